@@ -1,6 +1,29 @@
-use std::collections::HashMap;
-
-use rand::{prelude::SliceRandom, rng, rngs::ThreadRng, Rng};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    time::Duration,
+};
+
+use rand::{prelude::SliceRandom, rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    bot::BotStrength,
+    calculator::{Calculator, InputMode},
+    conundrum::{Conundrum, ANSWER_TIME, CONUNDRUM_POINTS},
+    daily::Date,
+    episode::{Episode, EpisodeFormat, RoundKind},
+    expression::{self, EvaluationError, Expression, ParseError, Rules},
+    hint::{self, HINT_COUNT},
+    history::{RoundRecord, Statistics},
+    input_history::{Candidate, InputHistory},
+    letters::{self, LetterKind, LetterPiles, WordError, LETTER_COUNT},
+    line_editor::{self, Edit},
+    player::{Player, PlayerMode},
+    puzzle_code::Puzzle,
+    scoring::{Scoring, HINT_PENALTY},
+    solver::{self, Solution},
+};
 
 #[derive(Debug, Default)]
 pub enum CurrentScreen {
@@ -10,26 +33,178 @@ pub enum CurrentScreen {
     PickingNumbers,
     Playing,
     DisplayingResult,
+    Statistics,
+
+    PickingLetters,
+    PlayingLetters,
+    LettersResult,
+
+    PlayingConundrum,
+    ConundrumResult,
+
+    /// Final scores, once every round of an episode is played
+    EpisodeSummary,
 }
 
 const LARGE_NUMBER_COUNT: usize = 4;
 const SMALL_NUMBER_COUNT: usize = 20;
 
-#[derive(Debug, Default)]
+pub const MIN_TARGET: u32 = 100;
+pub const MAX_TARGET: u32 = 1_000;
+
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(30);
+
+/// Delay between numbers appearing when several are requested at once
+const REVEAL_INTERVAL: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberSize {
+    Large,
+    Small,
+}
+
+/// Reason a solution attempt cannot be scored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolutionError {
+    Empty,
+    Syntax(ParseError),
+    TooManyNumbers(usize),
+    NumberNotSelected(u32),
+    NumberUsedTooOften(u32),
+    Calculation(EvaluationError),
+}
+
+impl fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolutionError::Empty => write!(f, "Enter a solution"),
+            SolutionError::Syntax(error) => write!(f, "Syntax error: {error}"),
+            SolutionError::TooManyNumbers(count) => {
+                write!(f, "You can only use 6 numbers, but there are {count}")
+            }
+            SolutionError::NumberNotSelected(value) => {
+                write!(f, "{value} is not one of your numbers")
+            }
+            SolutionError::NumberUsedTooOften(value) => {
+                write!(f, "{value} is used more times than it was picked")
+            }
+            SolutionError::Calculation(error) => write!(f, "Invalid calculation: {error}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// Redraw the target, once all numbers are picked, until it can be hit exactly
+    pub solvable_targets_only: bool,
+
+    /// Whether intermediate results must follow the official rules
+    pub rules: Rules,
+
+    /// Time allowed to find a solution, once numbers are picked
+    pub time_limit: Duration,
+
+    /// Points awarded for each submitted solution
+    pub scoring: Scoring,
+
+    /// Number of players, and how they take turns
+    pub player_mode: PlayerMode,
+
+    /// Whether solutions are typed out or worked through a step at a time
+    pub input_mode: InputMode,
+
+    /// Rounds played in a full game, in order
+    pub episode_rounds: Vec<RoundKind>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            solvable_targets_only: false,
+            rules: Rules::default(),
+            time_limit: DEFAULT_TIME_LIMIT,
+            scoring: Scoring::default(),
+            player_mode: PlayerMode::default(),
+            input_mode: InputMode::default(),
+            episode_rounds: EpisodeFormat::default().rounds(),
+        }
+    }
+}
+
+impl Settings {
+    /// Rules solutions are checked against.  The calculator only deals in positive whole numbers,
+    /// so step-by-step input always follows the official rules.
+    pub fn effective_rules(&self) -> Rules {
+        match self.input_mode {
+            InputMode::Expression => self.rules,
+            InputMode::Calculator => Rules::Strict,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct App {
     pub current_screen: CurrentScreen,
+    pub settings: Settings,
     pub available_small_numbers: [Option<u32>; SMALL_NUMBER_COUNT],
     pub available_large_numbers: [Option<u32>; LARGE_NUMBER_COUNT],
     pub selected_numbers: [Option<u32>; 6],
     pub target: u32,
-    pub value_input: String,
-    pub feedback: String,
-    pub rng: ThreadRng,
+    /// Shuffled vowels and consonants for the letters round
+    pub letter_piles: LetterPiles,
+    pub selected_letters: [Option<char>; LETTER_COUNT],
+    /// Longest words possible with the selected letters, worked out once the round is scored
+    pub longest_words: Vec<&'static str>,
+    /// Scrambled word for the conundrum round, once one is started
+    pub conundrum: Option<Conundrum>,
+    /// Full game being played, if rounds are chained together
+    pub episode: Option<Episode>,
+    /// Everyone playing this session, each with their own solution attempt
+    pub players: Vec<Player>,
+    /// Index in `players` of whoever is entering a solution
+    pub active_player: usize,
+    pub solution: Option<Solution>,
+    pub time_remaining: Duration,
+    /// Time left to answer the conundrum, once the active player has buzzed in
+    pub answer_time_remaining: Duration,
+    /// Rounds scored in this session
+    pub session_rounds: u32,
+    /// Numbers still to be revealed, after asking for several at once
+    pub pending_picks: VecDeque<NumberSize>,
+    reveal_elapsed: Duration,
+    /// Seed for `rng`, so the same round can be replayed
+    pub seed: u64,
+    /// Date of the daily puzzle being played, if this is a daily round
+    pub daily: Option<Date>,
+    /// Message for the player which does not belong to a particular screen element
+    pub notice: Option<String>,
+    /// Summary of saved rounds, loaded for the Statistics screen
+    pub statistics: Option<Statistics>,
+    /// Screen the Statistics screen was opened from, to go back to when it is closed
+    pub screen_before_statistics: CurrentScreen,
+    pub rng: ChaCha8Rng,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        App::new()
+    }
 }
 
 impl App {
     pub fn new() -> App {
-        let mut rng = rng();
+        App::with_settings(Settings::default())
+    }
+
+    pub fn with_settings(settings: Settings) -> App {
+        App::with_seed(settings, rng().random())
+    }
+
+    /// Creates a game where number shuffles, target and fireworks are all determined by `seed`.
+    /// `ChaCha8Rng` gives the same sequence on every platform, so shared seeds and daily puzzles
+    /// match between players, unlike `StdRng`, which may change between rand releases.
+    pub fn with_seed(settings: Settings, seed: u64) -> App {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
         // generate random large numbers
         let mut available_large_numbers = [25, 50, 75, 100];
@@ -41,17 +216,221 @@ impl App {
             [1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10];
         available_small_numbers.shuffle(&mut rng);
         let available_small_numbers = available_small_numbers.map(Some);
+        let target = rng.random_range(MIN_TARGET..MAX_TARGET);
+        let letter_piles = LetterPiles::new(&mut rng);
 
         App {
             current_screen: CurrentScreen::Introduction,
+            players: settings.player_mode.players(),
+            time_remaining: settings.time_limit,
+            settings,
             available_small_numbers,
             available_large_numbers,
             selected_numbers: [None; 6],
-            target: rng.random_range(100..1_000),
+            target,
+            letter_piles,
+            selected_letters: [None; LETTER_COUNT],
+            longest_words: Vec::new(),
+            conundrum: None,
+            episode: None,
+            seed,
+            daily: None,
+            notice: None,
+            statistics: None,
+            screen_before_statistics: CurrentScreen::default(),
             rng,
-            value_input: String::new(),
-            feedback: String::new(),
+            active_player: 0,
+            solution: None,
+            answer_time_remaining: ANSWER_TIME,
+            session_rounds: 0,
+            pending_picks: VecDeque::new(),
+            reveal_elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Creates the daily puzzle for `date`, which is the same for every player.  Numbers are
+    /// picked up front, the target is always exactly solvable and the official rules apply, so
+    /// results are comparable.  The player's settings are kept for later rounds.
+    pub fn daily(settings: Settings, date: Date) -> App {
+        let mut app = App::with_seed(settings, date.seed());
+        app.daily = Some(date);
+        let large_count = app.rng.random_range(0..=LARGE_NUMBER_COUNT);
+        for _ in 0..large_count {
+            app.pick_random_large_number();
+        }
+        for _ in large_count..app.selected_numbers.len() {
+            app.pick_random_small_number();
+        }
+        app.current_screen = CurrentScreen::Playing;
+        app
+    }
+
+    /// Creates a game for a shared puzzle, going straight to the Playing screen.
+    pub fn from_puzzle(settings: Settings, puzzle: &Puzzle) -> App {
+        let mut app = App::with_settings(settings);
+        for number in puzzle.numbers {
+            let available_numbers: &mut [Option<u32>] = if number > 10 {
+                &mut app.available_large_numbers
+            } else {
+                &mut app.available_small_numbers
+            };
+            if let Some(value) = available_numbers
+                .iter_mut()
+                .find(|value| **value == Some(number))
+            {
+                *value = None;
+            }
+        }
+        app.selected_numbers = puzzle.numbers.map(Some);
+        app.target = puzzle.target;
+        app.current_screen = CurrentScreen::Playing;
+        app
+    }
+
+    /// Code for sharing the current numbers and target, once all numbers are picked
+    pub fn puzzle_code(&self) -> Option<String> {
+        let mut numbers = [0; 6];
+        for (number, selected) in numbers.iter_mut().zip(self.selected_numbers) {
+            *number = selected?;
+        }
+        Puzzle::new(numbers, self.target)
+            .ok()
+            .map(|value| value.code())
+    }
+
+    /// Summary of the finished round, for saving to the score history.  Only rounds with a single
+    /// person playing are saved, so the history reflects one person’s play.
+    pub fn round_record(&self) -> Option<RoundRecord> {
+        let mut people = self.players.iter().filter(|player| player.bot.is_none());
+        let (Some(player), None) = (people.next(), people.next()) else {
+            return None;
+        };
+        let mut numbers = [0; 6];
+        for (number, selected) in numbers.iter_mut().zip(self.selected_numbers) {
+            *number = selected?;
+        }
+        Some(RoundRecord {
+            numbers,
+            target: self.target,
+            expression: player.value_input.trim().to_string(),
+            distance: self.check_input(&player.value_input).ok(),
+            time_taken: self.settings.time_limit.saturating_sub(self.time_remaining),
+        })
+    }
+
+    /// Starts another round with the same settings, keeping the session score.  The new round is
+    /// seeded from this one, so a sequence of seeded rounds can be replayed too, except after the
+    /// daily puzzle, whose seed every player shares.
+    pub fn next_round(&self) -> App {
+        let seed = if self.daily.is_some() {
+            rng().random()
+        } else {
+            self.seed.wrapping_add(1)
+        };
+        let mut app = App::with_seed(self.settings.clone(), seed);
+        app.players = self.players.iter().map(Player::next_round).collect();
+        app.session_rounds = self.session_rounds;
+        app.current_screen = CurrentScreen::PickingNumbers;
+        app
+    }
+
+    /// Moves on to the daily puzzle for `date`, keeping the players and session score, as
+    /// [`App::next_round`] does
+    pub fn start_daily(&self, date: Date) -> App {
+        let mut app = App::daily(self.settings.clone(), date);
+        app.players = self.players.iter().map(Player::next_round).collect();
+        app.session_rounds = self.session_rounds;
+        app
+    }
+
+    /// Shows `statistics`, remembering the current screen to go back to
+    pub fn open_statistics(&mut self, statistics: Statistics) {
+        self.statistics = Some(statistics);
+        self.screen_before_statistics =
+            std::mem::replace(&mut self.current_screen, CurrentScreen::Statistics);
+    }
+
+    /// Goes back to the screen the Statistics screen was opened from
+    pub fn close_statistics(&mut self) {
+        self.current_screen = std::mem::take(&mut self.screen_before_statistics);
+    }
+
+    /// Starts a full game, with fresh scores, beginning with its first round
+    pub fn start_episode(&mut self) {
+        let episode = Episode::new(self.settings.episode_rounds.clone());
+        let first_round = episode.current_round();
+        self.episode = Some(episode);
+        self.players = self.settings.player_mode.players();
+        self.session_rounds = 0;
+        if let Some(kind) = first_round {
+            self.start_round(kind);
+        }
+    }
+
+    /// Moves to the first screen of a round of `kind`
+    pub fn start_round(&mut self, kind: RoundKind) {
+        match kind {
+            RoundKind::Letters => self.current_screen = CurrentScreen::PickingLetters,
+            RoundKind::Numbers => self.current_screen = CurrentScreen::PickingNumbers,
+            RoundKind::Conundrum => self.start_conundrum(),
+        }
+    }
+
+    /// Records the finished round on the episode scoreboard, then starts the next round of the
+    /// episode, or shows the final scores after the last one.
+    pub fn next_episode_round(&self) -> App {
+        let mut app = self.next_round();
+        let Some(mut episode) = self.episode.clone() else {
+            return app;
+        };
+        let points = self
+            .players
+            .iter()
+            .map(|player| player.round_points.unwrap_or_default())
+            .collect();
+        let next_round = episode.finish_round(points);
+        app.episode = Some(episode);
+        match next_round {
+            Some(kind) => app.start_round(kind),
+            None => app.current_screen = CurrentScreen::EpisodeSummary,
+        }
+        app
+    }
+
+    /// Counts down the clock while playing, submitting the current solution, word or conundrum
+    /// answer once time is up.  The clock stops once a player buzzes in for the conundrum.
+    /// Returns `true` on the tick the time runs out.
+    pub fn on_tick(&mut self, elapsed: Duration) -> bool {
+        if matches!(self.current_screen, CurrentScreen::PickingNumbers) {
+            self.reveal_pending_pick(elapsed);
+        }
+        if !matches!(
+            self.current_screen,
+            CurrentScreen::Playing
+                | CurrentScreen::PlayingLetters
+                | CurrentScreen::PlayingConundrum
+        ) {
+            return false;
+        }
+        if self.player().buzz_time.is_some() {
+            // the main clock stays stopped while the player who buzzed in answers
+            self.answer_time_remaining = self.answer_time_remaining.saturating_sub(elapsed);
+            if self.answer_time_remaining.is_zero() {
+                self.submit_conundrum();
+                return true;
+            }
+            return false;
         }
+        self.time_remaining = self.time_remaining.saturating_sub(elapsed);
+        if self.time_remaining.is_zero() {
+            match self.current_screen {
+                CurrentScreen::PlayingLetters => self.submit_word(),
+                CurrentScreen::PlayingConundrum => self.submit_conundrum(),
+                _ => self.submit_solution(),
+            }
+            return true;
+        }
+        false
     }
 
     fn random_available_large_number_index(&mut self) -> Option<usize> {
@@ -97,7 +476,21 @@ impl App {
         }
     }
 
+    /// Picks a random large number, unless a requested mix of numbers is still being revealed
     pub fn pick_random_large_number(&mut self) {
+        if self.pending_picks.is_empty() {
+            self.draw_large_number();
+        }
+    }
+
+    /// Picks a random small number, unless a requested mix of numbers is still being revealed
+    pub fn pick_random_small_number(&mut self) {
+        if self.pending_picks.is_empty() {
+            self.draw_small_number();
+        }
+    }
+
+    fn draw_large_number(&mut self) {
         if let Some(index_value) = self.random_available_large_number_index() {
             let result = self.available_large_numbers[index_value];
             let picked_index = self.selected_numbers.iter().position(|&val| val.is_none());
@@ -105,12 +498,15 @@ impl App {
                 if result.is_some() {
                     self.selected_numbers[picked_index_value] = result;
                     self.available_large_numbers[index_value] = None;
-                };
+                }
             }
         }
+        if self.is_number_selection_complete() {
+            self.on_number_selection_complete();
+        }
     }
 
-    pub fn pick_random_small_number(&mut self) {
+    fn draw_small_number(&mut self) {
         if let Some(index_value) = self.random_available_small_number_index() {
             let result = self.available_small_numbers[index_value];
             let picked_index = self.selected_numbers.iter().position(|&val| val.is_none());
@@ -118,50 +514,464 @@ impl App {
                 if result.is_some() {
                     self.selected_numbers[picked_index_value] = result;
                     self.available_small_numbers[index_value] = None;
-                };
+                }
             }
         }
+        if self.is_number_selection_complete() {
+            self.on_number_selection_complete();
+        }
     }
 
-    pub fn check_solution(&self) -> Option<u32> {
-        let input = self.value_input.trim();
+    /// Asks for `large_count` large numbers, with the rest of the six small, as contestants do on
+    /// the show.  Only available before any numbers are picked.
+    pub fn request_large_numbers(&mut self, large_count: usize) {
+        let large_count = large_count.min(LARGE_NUMBER_COUNT);
+        if !self.pending_picks.is_empty() || self.selected_numbers.iter().any(Option::is_some) {
+            return;
+        }
 
-        if input.trim().is_empty() {
-            return None;
+        self.pending_picks.extend(
+            std::iter::repeat(NumberSize::Large)
+                .take(large_count)
+                .chain(std::iter::repeat(NumberSize::Small))
+                .take(self.selected_numbers.len()),
+        );
+        self.reveal_elapsed = Duration::ZERO;
+    }
+
+    fn reveal_pending_pick(&mut self, elapsed: Duration) {
+        if self.pending_picks.is_empty() {
+            return;
+        }
+        self.reveal_elapsed += elapsed;
+        if self.reveal_elapsed < REVEAL_INTERVAL {
+            return;
+        }
+        self.reveal_elapsed = Duration::ZERO;
+        match self.pending_picks.pop_front() {
+            Some(NumberSize::Large) => self.draw_large_number(),
+            Some(NumberSize::Small) => self.draw_small_number(),
+            None => {}
         }
+    }
 
-        let solution_numbers = get_solution_numbers(input);
+    /// Rules this round is checked against.  The daily puzzle always follows the official rules.
+    pub fn effective_rules(&self) -> Rules {
+        if self.daily.is_some() {
+            Rules::Strict
+        } else {
+            self.settings.effective_rules()
+        }
+    }
 
-        if solution_numbers.len() > 6 {
-            return None;
+    /// Whether this round's target must be exactly solvable, as it always is for the daily puzzle
+    pub fn solvable_targets_only(&self) -> bool {
+        self.daily.is_some() || self.settings.solvable_targets_only
+    }
+
+    fn on_number_selection_complete(&mut self) {
+        if self.solvable_targets_only() {
+            self.redraw_unsolvable_target();
+        }
+    }
+
+    fn redraw_unsolvable_target(&mut self) {
+        let numbers: Vec<u32> = self.selected_numbers.iter().flatten().copied().collect();
+        let reachable = solver::reachable_values(&numbers);
+        if !(MIN_TARGET..MAX_TARGET).any(|value| reachable.contains(&value)) {
+            return;
         }
+        while !reachable.contains(&self.target) {
+            self.target = self.rng.random_range(MIN_TARGET..MAX_TARGET);
+        }
+    }
 
-        if !check_solution_numbers(&solution_numbers, &self.selected_numbers) {
-            return None;
+    /// Player currently entering a solution
+    pub fn player(&self) -> &Player {
+        &self.players[self.active_player]
+    }
+
+    pub fn player_mut(&mut self) -> &mut Player {
+        &mut self.players[self.active_player]
+    }
+
+    /// `true` once every player has submitted a solution, or a word, for this round
+    pub fn is_round_complete(&self) -> bool {
+        matches!(
+            self.current_screen,
+            CurrentScreen::DisplayingResult
+                | CurrentScreen::LettersResult
+                | CurrentScreen::ConundrumResult
+        )
+    }
+
+    /// Passes the turn on to the next player, with a full clock.  Computer players answer
+    /// straight away, using `play_bot`.  Returns `true` once everyone has answered.
+    fn next_turn(&mut self, play_bot: fn(&mut App, BotStrength)) -> bool {
+        while self.active_player + 1 < self.players.len() {
+            self.active_player += 1;
+            if let Some(strength) = self.player().bot {
+                play_bot(self, strength);
+            } else {
+                self.time_remaining = self.settings.time_limit;
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Submits the active player's solution, or their best attempt this round if that was closer.
+    /// The next player then takes their turn, until everyone has submitted.  Finally, the round
+    /// is scored and the best possible answer worked out, so it can be shown alongside.
+    pub fn submit_solution(&mut self) {
+        self.keep_best_attempt();
+        if !self.next_turn(App::play_bot_turn) {
+            return;
         }
 
-        check_solution_calculation(input, self.target)
+        let numbers: Vec<u32> = self.selected_numbers.iter().flatten().copied().collect();
+        self.solution = solver::solve(&numbers, self.target);
+        self.award_points();
+        self.current_screen = CurrentScreen::DisplayingResult;
     }
-}
 
-fn check_solution_calculation(solution: &str, target: u32) -> Option<u32> {
-    if let Ok(calculation_value) = num_parser::eval(solution) {
-        let calculation_value: u32 = match calculation_value.as_int() {
-            Ok(value) => value
-                .try_into()
-                // currently largest result is 100 * 75 * 50 * 25 * 10 * 10 = 937_500_000
-                .expect("Should be able to represent calculation result as a 64-bit integer"),
-            Err(_) => return None,
+    /// Applies `edit` to the active player's typed solution, saving the input beforehand so the
+    /// edit can be undone.  A valid solution is saved as a candidate whenever an edit changes it,
+    /// including extending it, so the player can scroll back to it.  Returns `true` if the input
+    /// changed.
+    pub fn edit_input(&mut self, edit: Edit) -> bool {
+        let distance = self.check_solution().ok();
+        let player = self.player_mut();
+        let (before, cursor) = (player.value_input.clone(), player.cursor);
+        if !line_editor::apply(&mut player.value_input, &mut player.cursor, edit) {
+            return false;
+        }
+        player.input_history.record_edit(&before, cursor);
+        if let Some(distance) = distance {
+            player.input_history.record_candidate(&before, distance);
+        }
+        self.update_best_attempt();
+        true
+    }
+
+    /// Moves through the active player's input history with `navigate`, such as
+    /// [`InputHistory::undo`].  Returns `true` if the input changed.
+    pub fn navigate_input(
+        &mut self,
+        navigate: fn(&mut InputHistory, &mut String, &mut usize) -> bool,
+    ) -> bool {
+        let player = self.player_mut();
+        let changed = navigate(
+            &mut player.input_history,
+            &mut player.value_input,
+            &mut player.cursor,
+        );
+        if changed {
+            self.update_best_attempt();
+        }
+        changed
+    }
+
+    /// Remembers the active player's current solution if it is the closest yet this round
+    fn update_best_attempt(&mut self) {
+        let Ok(distance) = self.check_solution() else {
+            return;
+        };
+        let player = self.player_mut();
+        if player
+            .best_attempt
+            .as_ref()
+            .is_some_and(|best| best.distance <= distance)
+        {
+            return;
+        }
+        player.best_attempt = Some(Candidate {
+            expression: player.value_input.trim().to_string(),
+            distance,
+        });
+    }
+
+    /// Swaps the active player's final input for their best attempt, if that was closer, so a
+    /// good answer is not lost to later editing.
+    fn keep_best_attempt(&mut self) {
+        let final_distance = self.check_solution().ok();
+        let player = self.player_mut();
+        let Some(best) = &player.best_attempt else {
+            return;
+        };
+        if final_distance.map_or(true, |value| best.distance < value) {
+            let final_input = std::mem::replace(&mut player.value_input, best.expression.clone());
+            player.final_input = Some(final_input);
+        }
+    }
+
+    /// Applies `action` to the active player's step-by-step working, then takes their answer
+    /// from the latest result, so it is scored just like a typed solution.
+    pub fn update_calculator(&mut self, action: impl FnOnce(&mut Calculator)) {
+        let numbers: Vec<u32> = self.selected_numbers.iter().flatten().copied().collect();
+        let player = self.player_mut();
+        let calculator = player
+            .calculator
+            .get_or_insert_with(|| Calculator::new(&numbers));
+        action(calculator);
+        player.value_input = calculator.answer();
+        self.update_best_attempt();
+    }
+
+    /// Reveals the active player's next hint, if any are left.  Each hint costs points, once the
+    /// round is scored.
+    pub fn request_hint(&mut self) {
+        if self.player().hints.len() >= HINT_COUNT {
+            return;
+        }
+        if self.solution.is_none() {
+            let numbers: Vec<u32> = self.selected_numbers.iter().flatten().copied().collect();
+            self.solution = solver::solve(&numbers, self.target);
+        }
+        let level = self.player().hints.len();
+        if let Some(text) = self
+            .solution
+            .as_ref()
+            .and_then(|value| hint::hint_text(value, level))
+        {
+            self.player_mut().hints.push(text);
+        }
+    }
+
+    fn play_bot_turn(&mut self, strength: BotStrength) {
+        let numbers: Vec<u32> = self.selected_numbers.iter().flatten().copied().collect();
+        let max_steps = self.rng.random_range(strength.max_steps());
+        let expression = solver::solve_in_steps(&numbers, self.target, max_steps)
+            .map(|value| value.expression)
+            .unwrap_or_default();
+        self.player_mut().value_input = expression;
+    }
+
+    /// Scores each player's solution.  As on the show, only the closest valid solution earns
+    /// points, with tied players all scoring.
+    fn award_points(&mut self) {
+        let distances: Vec<Option<u32>> = self
+            .players
+            .iter()
+            .map(|player| self.check_input(&player.value_input).ok())
+            .collect();
+        let closest = distances.iter().flatten().min().copied();
+        for (player, distance) in self.players.iter_mut().zip(distances) {
+            let points = if distance.is_some() && distance == closest {
+                let penalty = HINT_PENALTY
+                    * u32::try_from(player.hints.len()).expect("Hint count should fit in u32");
+                self.settings
+                    .scoring
+                    .points(distance)
+                    .saturating_sub(penalty)
+            } else {
+                0
+            };
+            player.round_points = Some(points);
+            player.score += points;
+        }
+        self.session_rounds += 1;
+    }
+
+    pub fn is_letter_selection_complete(&self) -> bool {
+        self.selected_letters.iter().all(Option::is_some)
+    }
+
+    /// Draws the next letter of `kind`, unless that would leave too few vowels or consonants
+    pub fn pick_letter(&mut self, kind: LetterKind) {
+        if !letters::can_pick(kind, &self.selected_letters) {
+            return;
+        }
+        let Some(slot) = self
+            .selected_letters
+            .iter_mut()
+            .find(|value| value.is_none())
+        else {
+            return;
+        };
+        *slot = self.letter_piles.draw(kind);
+    }
+
+    fn letters(&self) -> Vec<char> {
+        self.selected_letters.iter().flatten().copied().collect()
+    }
+
+    /// Applies `edit` to the active player's word.  Only letters are typed, and they are shown
+    /// in capitals, like the letters on the board.
+    pub fn edit_word(&mut self, edit: Edit) {
+        let edit = match edit {
+            Edit::Insert(value) if value.is_ascii_alphabetic() => {
+                Edit::Insert(value.to_ascii_uppercase())
+            }
+            Edit::Insert(_) => return,
+            other => other,
+        };
+        let player = self.player_mut();
+        line_editor::apply(&mut player.value_input, &mut player.cursor, edit);
+    }
+
+    /// Submits the active player's word, passing the turn on as for the numbers round.  Once
+    /// everyone has submitted, the longest word wins and the longest possible words are found.
+    pub fn submit_word(&mut self) {
+        if !self.next_turn(App::play_bot_word) {
+            return;
+        }
+
+        self.longest_words = letters::longest_words(&self.letters());
+        self.award_word_points();
+        self.current_screen = CurrentScreen::LettersResult;
+    }
+
+    fn play_bot_word(&mut self, strength: BotStrength) {
+        let max_length = self.rng.random_range(strength.max_word_length());
+        let word = letters::words_from(&self.letters())
+            .into_iter()
+            .find(|value| value.len() <= max_length)
+            .unwrap_or_default();
+        self.player_mut().value_input = word.to_ascii_uppercase();
+    }
+
+    /// Scores each player's word, following the show: only the longest valid word scores, a
+    /// point per letter, or double for using all nine letters.  Tied players all score.
+    fn award_word_points(&mut self) {
+        let lengths: Vec<Option<usize>> = self
+            .players
+            .iter()
+            .map(|player| self.check_word(&player.value_input).ok())
+            .collect();
+        let longest = lengths.iter().flatten().max().copied();
+        for (player, length) in self.players.iter_mut().zip(lengths) {
+            let points = match length {
+                Some(value) if length == longest => letters::points(value),
+                _ => 0,
+            };
+            player.round_points = Some(points);
+            player.score += points;
+        }
+        self.session_rounds += 1;
+    }
+
+    /// Checks a word against this round's letters and the word list, returning its length
+    pub fn check_word(&self, word: &str) -> Result<usize, WordError> {
+        letters::check_word(word, &self.letters())
+    }
+
+    /// Scrambles a new conundrum and starts the clock
+    pub fn start_conundrum(&mut self) {
+        self.conundrum = Some(Conundrum::new(&mut self.rng));
+        self.time_remaining = self.settings.time_limit;
+        self.current_screen = CurrentScreen::PlayingConundrum;
+    }
+
+    /// Stops the clock for the active player, who then has [`ANSWER_TIME`] to type their answer
+    /// before it is submitted
+    pub fn buzz_in(&mut self) {
+        let buzz_time = self.settings.time_limit.saturating_sub(self.time_remaining);
+        if self.player().buzz_time.is_none() {
+            self.player_mut().buzz_time = Some(buzz_time);
+            self.answer_time_remaining = ANSWER_TIME;
+        }
+    }
+
+    /// Submits the active player's conundrum answer, passing the turn on as for the numbers
+    /// round.  Once everyone has answered, the intended word can be revealed.
+    pub fn submit_conundrum(&mut self) {
+        if !matches!(self.current_screen, CurrentScreen::PlayingConundrum)
+            || !self.next_turn(App::play_bot_conundrum)
+        {
+            return;
+        }
+
+        self.award_conundrum_points();
+        self.current_screen = CurrentScreen::ConundrumResult;
+    }
+
+    fn play_bot_conundrum(&mut self, strength: BotStrength) {
+        if !self.rng.random_bool(strength.conundrum_odds()) {
+            return;
+        }
+        let Some(word) = self.conundrum.as_ref().map(|value| value.word) else {
+            return;
         };
-        if calculation_value > target {
-            return Some(calculation_value - target);
+        let buzz_time = self.settings.time_limit.mul_f64(self.rng.random());
+        let player = self.player_mut();
+        player.value_input = word.to_ascii_uppercase();
+        player.buzz_time = Some(buzz_time);
+    }
+
+    /// Scores the conundrum: only whoever buzzed in first with a correct answer scores, as on
+    /// the show, with tied players all scoring.
+    fn award_conundrum_points(&mut self) {
+        let buzz_times: Vec<Option<Duration>> = self
+            .players
+            .iter()
+            .map(|player| {
+                player
+                    .buzz_time
+                    .filter(|_| self.check_conundrum(&player.value_input))
+            })
+            .collect();
+        let fastest = buzz_times.iter().flatten().min().copied();
+        for (player, buzz_time) in self.players.iter_mut().zip(buzz_times) {
+            let points = if buzz_time.is_some() && buzz_time == fastest {
+                CONUNDRUM_POINTS
+            } else {
+                0
+            };
+            player.round_points = Some(points);
+            player.score += points;
+        }
+        self.session_rounds += 1;
+    }
+
+    /// `true` if `answer` solves this round's conundrum
+    pub fn check_conundrum(&self, answer: &str) -> bool {
+        self.conundrum
+            .as_ref()
+            .is_some_and(|value| value.is_solution(answer))
+    }
+
+    /// Checks the active player's solution
+    pub fn check_solution(&self) -> Result<u32, SolutionError> {
+        self.check_input(&self.player().value_input)
+    }
+
+    /// Checks a solution against this round's numbers and target, returning its distance from
+    /// the target
+    pub fn check_input(&self, input: &str) -> Result<u32, SolutionError> {
+        let input = input.trim();
+
+        if input.is_empty() {
+            return Err(SolutionError::Empty);
+        }
+
+        let expression = expression::parse(input).map_err(SolutionError::Syntax)?;
+        let solution_numbers = expression.numbers();
+
+        if solution_numbers.len() > 6 {
+            return Err(SolutionError::TooManyNumbers(solution_numbers.len()));
         }
-        return Some(target - calculation_value);
+
+        check_solution_numbers(&solution_numbers, &self.selected_numbers)?;
+
+        check_solution_calculation(&expression, self.target, self.effective_rules())
     }
-    None
 }
 
-fn check_solution_numbers(solution_numbers: &[u32], selected_numbers: &[Option<u32>; 6]) -> bool {
+fn check_solution_calculation(
+    solution: &Expression,
+    target: u32,
+    rules: Rules,
+) -> Result<u32, SolutionError> {
+    // currently largest result is 100 * 75 * 50 * 25 * 10 * 10 = 937_500_000
+    let calculation_value = solution.value(rules).map_err(SolutionError::Calculation)?;
+    Ok(calculation_value.abs_diff(target))
+}
+
+fn check_solution_numbers(
+    solution_numbers: &[u32],
+    selected_numbers: &[Option<u32>; 6],
+) -> Result<(), SolutionError> {
     let unused_number_values: [u32; 6] = selected_numbers.map(|val| {
         val.expect("Solution should be checked against complete set of selected numbers")
     });
@@ -178,7 +988,10 @@ fn check_solution_numbers(solution_numbers: &[u32], selected_numbers: &[Option<u
     // Remove matching instances from frequency map matching solution numbers
     for number in solution_numbers {
         match unused_numbers.get(number) {
-            None => return false,
+            None if unused_number_values.contains(number) => {
+                return Err(SolutionError::NumberUsedTooOften(*number));
+            }
+            None => return Err(SolutionError::NumberNotSelected(*number)),
             Some(1) => {
                 unused_numbers.remove(number);
             }
@@ -187,30 +1000,30 @@ fn check_solution_numbers(solution_numbers: &[u32], selected_numbers: &[Option<u
             }
         }
     }
-    true
-}
-
-fn get_solution_numbers(solution: &str) -> Vec<u32> {
-    let result =
-        solution
-            .split(|c: char| !c.is_ascii_digit())
-            .fold(Vec::<u32>::new(), |mut accum, val| {
-                if !val.is_empty() {
-                    if let Ok(value) = val.parse::<u32>() {
-                        accum.push(value);
-                    };
-                }
-                accum
-            });
-    result
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        check_solution_calculation, check_solution_numbers, get_solution_numbers, App,
-        LARGE_NUMBER_COUNT, SMALL_NUMBER_COUNT,
+        check_solution_calculation, check_solution_numbers, App, CurrentScreen, Settings,
+        SolutionError, DEFAULT_TIME_LIMIT, LARGE_NUMBER_COUNT, REVEAL_INTERVAL, SMALL_NUMBER_COUNT,
     };
+    use crate::{
+        bot::BotStrength,
+        calculator::InputMode,
+        conundrum::ANSWER_TIME,
+        daily::Date,
+        episode::EpisodeFormat,
+        expression::{parse, EvaluationError, Operator, ParseError, Rules},
+        history::Statistics,
+        input_history::InputHistory,
+        letters::{self, LetterKind, LETTER_COUNT},
+        line_editor::Edit,
+        player::PlayerMode,
+        puzzle_code::Puzzle,
+    };
+    use std::time::Duration;
 
     #[test]
     fn random_available_large_number_index_returns_only_valid_index_as_expected() {
@@ -280,94 +1093,690 @@ mod tests {
     }
 
     #[test]
-    fn is_number_selection_complete_returns_false_as_expected() {
+    fn with_seed_reproduces_the_same_round() {
         // arrange
-        let mut app = App::new();
+        let mut app = App::with_seed(Settings::default(), 1_234);
+        let mut other_app = App::with_seed(Settings::default(), 1_234);
 
         // act
-        let result = app.is_number_selection_complete();
+        for app in [&mut app, &mut other_app] {
+            app.pick_random_large_number();
+            app.pick_random_large_number();
+            for _ in 0..4 {
+                app.pick_random_small_number();
+            }
+        }
 
         // assert
-        assert!(!result);
+        assert_eq!(
+            app.available_large_numbers,
+            other_app.available_large_numbers
+        );
+        assert_eq!(
+            app.available_small_numbers,
+            other_app.available_small_numbers
+        );
+        assert_eq!(app.selected_numbers, other_app.selected_numbers);
+        assert_eq!(app.target, other_app.target);
+        assert_eq!(app.next_round().target, other_app.next_round().target);
+    }
 
+    #[test]
+    fn daily_gives_every_player_the_same_puzzle() {
         // arrange
-        app.selected_numbers[0] = Some(1);
-        app.selected_numbers[1] = Some(1);
+        let date = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
+        let other_settings = Settings {
+            time_limit: Duration::from_secs(60),
+            ..Settings::default()
+        };
 
         // act
-        let result = app.is_number_selection_complete();
+        let app = App::daily(Settings::default(), date);
+        let other_app = App::daily(other_settings, date);
 
         // assert
-        assert!(!result);
+        assert!(app.is_number_selection_complete());
+        assert!(matches!(app.current_screen, CurrentScreen::Playing));
+        assert_eq!(app.selected_numbers, other_app.selected_numbers);
+        assert_eq!(app.target, other_app.target);
+        assert_eq!(app.daily, Some(date));
     }
 
     #[test]
-    fn is_number_selection_complete_returns_true_as_expected() {
+    fn daily_puzzle_is_pinned_for_a_date() {
         // arrange
-        let mut app = App::new();
-        app.pick_random_large_number();
-        app.pick_random_large_number();
-        app.pick_random_large_number();
-        app.pick_random_small_number();
-        app.pick_random_small_number();
-        app.pick_random_small_number();
+        let date = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
 
         // act
-        let result = app.is_number_selection_complete();
+        let app = App::daily(Settings::default(), date);
 
         // assert
-        assert!(result);
+        assert_eq!(
+            app.selected_numbers,
+            [Some(100), Some(25), Some(75), Some(1), Some(10), Some(4)]
+        );
+        assert_eq!(app.target, 894);
     }
 
     #[test]
-    fn check_solution_returns_none_for_empty_solution() {
+    fn start_daily_keeps_players_and_score_with_strict_rules() {
         // arrange
-        let mut app = App::new();
-        app.value_input = String::from("  ");
+        let settings = Settings {
+            rules: Rules::Lenient,
+            player_mode: PlayerMode::HotSeat,
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.players[1].score = 7;
+        app.session_rounds = 2;
+        let date = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
 
         // act
-        let result = app.check_solution();
+        let daily_app = app.start_daily(date);
 
-        assert_eq!(result, None);
+        let next_app = daily_app.next_round();
+
+        // assert
+        assert_eq!(daily_app.effective_rules(), Rules::Strict);
+        assert!(daily_app.solvable_targets_only());
+        assert_eq!(daily_app.players.len(), 2);
+        assert_eq!(daily_app.players[1].score, 7);
+        assert_eq!(daily_app.session_rounds, 2);
+        assert_eq!(
+            daily_app.target,
+            App::daily(Settings::default(), date).target
+        );
+        assert_eq!(next_app.daily, None);
+        assert_eq!(next_app.effective_rules(), Rules::Lenient);
+        assert!(!next_app.solvable_targets_only());
+        assert_ne!(next_app.seed, date.seed().wrapping_add(1));
+    }
 
+    #[test]
+    fn close_statistics_returns_to_screen_it_was_opened_from() {
         // arrange
-        app.value_input = String::new();
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::DisplayingResult;
+        let mut intro_app = App::new();
 
         // act
-        let result = app.check_solution();
+        app.open_statistics(Statistics::from_records(&[]));
+        let opened = matches!(app.current_screen, CurrentScreen::Statistics);
+        app.close_statistics();
+        intro_app.open_statistics(Statistics::from_records(&[]));
+        intro_app.close_statistics();
 
         // assert
-        assert_eq!(result, None);
+        assert!(opened);
+        assert!(matches!(
+            app.current_screen,
+            CurrentScreen::DisplayingResult
+        ));
+        assert!(matches!(
+            intro_app.current_screen,
+            CurrentScreen::Introduction
+        ));
     }
 
     #[test]
-    fn check_solution_returns_none_for_solution_using_non_selected_numbers() {
+    fn from_puzzle_loads_shared_puzzle() {
+        // arrange
+        let mut app = App::new();
+        app.pick_random_large_number();
+        for _ in 0..5 {
+            app.pick_random_small_number();
+        }
+        let code = app.puzzle_code().unwrap();
+        let puzzle = Puzzle::from_code(&code).unwrap();
+
+        // act
+        let result = App::from_puzzle(Settings::default(), &puzzle);
+
+        // assert
+        assert!(matches!(result.current_screen, CurrentScreen::Playing));
+        assert_eq!(result.selected_numbers, app.selected_numbers);
+        assert_eq!(result.target, app.target);
+        assert_eq!(
+            result.available_large_numbers.iter().flatten().count(),
+            LARGE_NUMBER_COUNT - 1
+        );
+        assert_eq!(
+            result.available_small_numbers.iter().flatten().count(),
+            SMALL_NUMBER_COUNT - 5
+        );
+    }
+
+    #[test]
+    fn submit_solution_adds_points_to_session_score() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 6 + 3");
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        app.submit_solution();
+        let mut next_app = app.next_round();
+        next_app.selected_numbers = app.selected_numbers;
+        next_app.target = 812;
+        next_app.player_mut().value_input = String::from("2 * 3");
+        next_app.submit_solution();
+
+        // assert
+        assert_eq!(app.players[0].round_points, Some(7));
+        assert_eq!(app.players[0].score, 7);
+        assert_eq!(next_app.players[0].round_points, Some(0));
+        assert_eq!(next_app.players[0].score, 7);
+        assert_eq!(next_app.session_rounds, 2);
+    }
+
+    #[test]
+    fn hot_seat_players_take_turns_and_closest_player_scores() {
+        // arrange
+        let settings = Settings {
+            player_mode: PlayerMode::HotSeat,
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+        app.on_tick(Duration::from_secs(20));
+
+        // act
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 6");
+        app.submit_solution();
+        let after_first_turn = (
+            app.active_player,
+            app.time_remaining,
+            app.is_round_complete(),
+        );
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 6 + 3");
+        app.submit_solution();
+
+        // assert
+        assert_eq!(after_first_turn, (1, app.settings.time_limit, false));
+        assert!(app.is_round_complete());
+        assert_eq!(app.players[0].round_points, Some(0));
+        assert_eq!(app.players[1].round_points, Some(7));
+        assert_eq!(app.round_record(), None);
+    }
+
+    #[test]
+    fn hot_seat_players_both_score_when_tied() {
+        // arrange
+        let settings = Settings {
+            player_mode: PlayerMode::HotSeat,
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 6");
+        app.submit_solution();
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 3 * 6");
+        app.submit_solution();
+
+        // assert
+        assert_eq!(app.players[0].score, 5);
+        assert_eq!(app.players[1].score, 5);
+    }
+
+    #[test]
+    fn computer_player_answers_once_player_submits() {
+        // arrange
+        let settings = Settings {
+            player_mode: PlayerMode::Computer(BotStrength::Expert),
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.selected_numbers = [Some(25), Some(50), Some(75), Some(100), Some(3), Some(6)];
+        app.target = 952;
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        app.player_mut().value_input = String::from("100 * 6 + 75 * 3");
+        app.submit_solution();
+
+        // assert
+        assert!(app.is_round_complete());
+        assert_eq!(app.check_input(&app.players[1].value_input), Ok(0));
+        assert_eq!(app.players[0].round_points, Some(0));
+        assert_eq!(app.players[1].round_points, Some(10));
+        assert_eq!(app.round_record().unwrap().distance, Some(127));
+    }
+
+    #[test]
+    fn hints_cost_points() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        for _ in 0..5 {
+            app.request_hint();
+        }
+        app.player_mut().value_input = String::from("50 * (9 + 7) + 6 + 3");
+        app.submit_solution();
+
+        // assert
+        assert_eq!(app.players[0].hints.len(), 3);
+        assert_eq!(app.players[0].hints[0], "The target can be hit exactly");
+        assert_eq!(app.players[0].round_points, Some(1));
+    }
+
+    #[test]
+    fn update_calculator_scores_latest_tile() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        app.update_calculator(|calculator| {
+            calculator.select_tile(5);
+            calculator.select_operator(Operator::Add);
+            calculator.select_tile(4);
+        });
+        app.update_calculator(|calculator| {
+            calculator.select_tile(1);
+            calculator.select_operator(Operator::Multiply);
+            calculator.select_tile(4);
+        });
+
+        // assert
+        assert_eq!(app.player().value_input, "50 * (9 + 7)");
+        assert_eq!(app.check_solution(), Ok(12));
+    }
+
+    #[test]
+    fn edit_input_saves_valid_solution_as_candidate_before_deleting() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+        for character in "50 * 9".chars() {
+            app.edit_input(Edit::Insert(character));
+        }
+
+        // act
+        app.edit_input(Edit::DeleteWordBackward);
+        app.edit_input(Edit::Insert('7'));
+        app.edit_input(Edit::Backspace);
+        let deleted = app.player().value_input.clone();
+        app.navigate_input(InputHistory::undo);
+        let undone = app.player().value_input.clone();
+        app.navigate_input(InputHistory::previous_candidate);
+        app.navigate_input(InputHistory::previous_candidate);
+
+        // assert
+        assert_eq!(deleted, "50 * ");
+        assert_eq!(undone, "50 * 7");
+        assert_eq!(app.player().value_input, "50 * 9");
+        let candidates = &app.player().input_history.candidates;
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(
+            (candidates[2].expression.as_str(), candidates[2].distance),
+            ("50 * 7", 462)
+        );
+    }
+
+    #[test]
+    fn edit_input_saves_valid_solution_as_candidate_before_extending() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+        for character in "50 * (9 + 7)".chars() {
+            app.edit_input(Edit::Insert(character));
+        }
+
+        // act
+        for character in " + 25".chars() {
+            app.edit_input(Edit::Insert(character));
+        }
+        app.navigate_input(InputHistory::previous_candidate);
+
+        // assert
+        assert_eq!(app.player().value_input, "50 * (9 + 7)");
+        assert!(app
+            .player()
+            .input_history
+            .candidates
+            .iter()
+            .any(|candidate| candidate.expression == "50 * (9 + 7)" && candidate.distance == 12));
+    }
+
+    #[test]
+    fn submit_solution_keeps_best_attempt() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.current_screen = CurrentScreen::Playing;
+        for character in "50 * (9 + 7) + 6 + 3".chars() {
+            app.edit_input(Edit::Insert(character));
+        }
+        for _ in 0..5 {
+            app.edit_input(Edit::Backspace);
+        }
+        let best_attempt = app.player().best_attempt.clone();
+
+        // act
+        app.submit_solution();
+
+        // assert
+        assert_eq!(
+            best_attempt.map(|value| value.distance),
+            Some(3),
+            "Best attempt should be the closest valid input so far"
+        );
+        assert_eq!(app.player().value_input, "50 * (9 + 7) + 6 + 3");
+        assert_eq!(app.player().final_input.as_deref(), Some("50 * (9 + 7) + "));
+        assert_eq!(app.player().round_points, Some(7));
+    }
+
+    #[test]
+    fn round_record_summarises_finished_round() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(25), Some(50), Some(3), Some(6), Some(7), Some(9)];
+        app.target = 812;
+        app.player_mut().value_input = String::from(" 50 * 6 + 25 ");
+        app.current_screen = CurrentScreen::Playing;
+        app.on_tick(Duration::from_millis(12_500));
+
+        // act
+        let result = app.round_record().unwrap();
+
+        // assert
+        assert_eq!(result.numbers, [25, 50, 3, 6, 7, 9]);
+        assert_eq!(result.expression, "50 * 6 + 25");
+        assert_eq!(result.distance, Some(487));
+        assert_eq!(result.time_taken, Duration::from_millis(12_500));
+    }
+
+    #[test]
+    fn pick_letter_keeps_enough_consonants() {
+        // arrange
+        let mut app = App::with_seed(Settings::default(), 1_234);
+
+        // act
+        for _ in 0..LETTER_COUNT {
+            app.pick_letter(LetterKind::Vowel);
+        }
+        let vowels_only = app.selected_letters;
+        for _ in 0..LETTER_COUNT {
+            app.pick_letter(LetterKind::Consonant);
+        }
+
+        // assert
+        assert_eq!(vowels_only.iter().flatten().count(), 5);
+        assert!(app.is_letter_selection_complete());
+        assert_eq!(
+            app.selected_letters
+                .iter()
+                .flatten()
+                .filter(|value| !letters::is_vowel(**value))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn submit_word_scores_longest_word_and_finds_best_possible() {
+        // arrange
+        let settings = Settings {
+            player_mode: PlayerMode::HotSeat,
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.selected_letters = ['S', 'T', 'A', 'R', 'E', 'D', 'O', 'N', 'L'].map(Some);
+        app.current_screen = CurrentScreen::PlayingLetters;
+        for letter in "stone".chars() {
+            app.edit_word(Edit::Insert(letter));
+        }
+        app.submit_word();
+        app.player_mut().value_input = String::from("ROASTED");
+
+        // act
+        app.submit_word();
+
+        // assert
+        assert!(app.is_round_complete());
+        assert_eq!(app.players[0].value_input, "STONE");
+        assert_eq!(app.players[0].round_points, Some(0));
+        assert_eq!(app.players[1].round_points, Some(7));
+        assert!(app.longest_words.iter().all(|word| word.len() >= 7));
+    }
+
+    #[test]
+    fn submit_conundrum_scores_first_correct_buzz() {
+        // arrange
+        let settings = Settings {
+            player_mode: PlayerMode::HotSeat,
+            ..Settings::default()
+        };
+        let mut app = App::with_settings(settings);
+        app.start_conundrum();
+        let word = app.conundrum.as_ref().unwrap().word.to_ascii_uppercase();
+        app.on_tick(Duration::from_secs(12));
+        app.buzz_in();
+        app.on_tick(Duration::from_secs(3));
+        app.player_mut().value_input.clone_from(&word);
+        app.submit_conundrum();
+        app.on_tick(Duration::from_secs(5));
+        app.buzz_in();
+        app.player_mut().value_input = word;
+
+        // act
+        app.submit_conundrum();
+
+        // assert
+        assert!(app.is_round_complete());
+        assert_eq!(app.players[0].buzz_time, Some(Duration::from_secs(12)));
+        assert_eq!(app.players[0].round_points, Some(0));
+        assert_eq!(app.players[1].round_points, Some(10));
+    }
+
+    #[test]
+    fn conundrum_answer_after_answer_time_scores_nothing() {
+        // arrange
+        let mut app = App::new();
+        app.start_conundrum();
+        let word = app.conundrum.as_ref().unwrap().word.to_ascii_uppercase();
+        app.buzz_in();
+
+        // act
+        let submitted = app.on_tick(ANSWER_TIME + Duration::from_secs(60));
+        app.player_mut().value_input = word;
+        app.submit_conundrum();
+
+        // assert
+        assert!(submitted);
+        assert!(matches!(app.current_screen, CurrentScreen::ConundrumResult));
+        assert_eq!(app.players[0].buzz_time, Some(Duration::ZERO));
+        assert_eq!(app.players[0].round_points, Some(0));
+        assert_eq!(app.players[0].score, 0);
+    }
+
+    #[test]
+    fn next_episode_round_moves_through_rounds_to_summary() {
+        // arrange
+        let settings = Settings {
+            episode_rounds: EpisodeFormat::Short.rounds(),
+            ..Settings::default()
+        };
+        let mut app = App::with_seed(settings, 1_234);
+        app.players[0].score = 25;
+        app.start_episode();
+        let first_screen = std::mem::take(&mut app.current_screen);
+        app.players[0].round_points = Some(6);
+        app.players[0].score = 6;
+
+        // act
+        app = app.next_episode_round();
+        let second_screen = std::mem::take(&mut app.current_screen);
+        app = app.next_episode_round();
+        let third_screen = std::mem::take(&mut app.current_screen);
+        app = app.next_episode_round();
+
+        // assert
+        assert!(matches!(first_screen, CurrentScreen::PickingLetters));
+        assert!(matches!(second_screen, CurrentScreen::PickingNumbers));
+        assert!(matches!(third_screen, CurrentScreen::PlayingConundrum));
+        assert!(matches!(app.current_screen, CurrentScreen::EpisodeSummary));
+        let episode = app.episode.unwrap();
+        assert_eq!(episode.scoreboard.len(), 3);
+        assert_eq!(episode.scoreboard[0].points, [6]);
+        assert_eq!(app.players[0].score, 6);
+    }
+
+    #[test]
+    fn is_number_selection_complete_returns_false_as_expected() {
+        // arrange
+        let mut app = App::new();
+
+        // act
+        let result = app.is_number_selection_complete();
+
+        // assert
+        assert!(!result);
+
+        // arrange
+        app.selected_numbers[0] = Some(1);
+        app.selected_numbers[1] = Some(1);
+
+        // act
+        let result = app.is_number_selection_complete();
+
+        // assert
+        assert!(!result);
+    }
+
+    #[test]
+    fn is_number_selection_complete_returns_true_as_expected() {
+        // arrange
+        let mut app = App::new();
+        app.pick_random_large_number();
+        app.pick_random_large_number();
+        app.pick_random_large_number();
+        app.pick_random_small_number();
+        app.pick_random_small_number();
+        app.pick_random_small_number();
+
+        // act
+        let result = app.is_number_selection_complete();
+
+        // assert
+        assert!(result);
+    }
+
+    #[test]
+    fn check_solution_returns_error_for_empty_solution() {
+        // arrange
+        let mut app = App::new();
+        app.player_mut().value_input = String::from("  ");
+
+        // act
+        let result = app.check_solution();
+
+        assert_eq!(result, Err(SolutionError::Empty));
+
+        // arrange
+        app.player_mut().value_input = String::new();
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert_eq!(result, Err(SolutionError::Empty));
+    }
+
+    #[test]
+    fn check_solution_returns_error_for_too_many_numbers_including_non_selected_one() {
         // arrange
         let mut app = App::new();
         app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
-        app.value_input = String::from("(1 + 2 + 3 + 4 + 5 + 6) * 7");
+        app.player_mut().value_input = String::from("(1 + 2 + 3 + 4 + 5 + 6) * 7");
         app.target = 147;
 
         // act
         let result = app.check_solution();
 
         // assert
-        assert_eq!(result, None);
+        assert_eq!(result, Err(SolutionError::TooManyNumbers(7)));
     }
 
     #[test]
-    fn check_solution_returns_none_for_solution_using_selected_number_too_many_times() {
+    fn check_solution_returns_error_for_solution_using_selected_number_too_many_times() {
         // arrange
         let mut app = App::new();
         app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
-        app.value_input = String::from("1 + 2 + 3 + 4 + 5 + 5");
+        app.player_mut().value_input = String::from("1 + 2 + 3 + 4 + 5 + 5");
         app.target = 20;
 
         // act
         let result = app.check_solution();
 
         // assert
-        assert_eq!(result, None);
+        assert_eq!(result, Err(SolutionError::NumberUsedTooOften(5)));
+    }
+
+    #[test]
+    fn check_solution_returns_error_for_syntax_error() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
+        app.player_mut().value_input = String::from("(1 + 2");
+        app.target = 3;
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert_eq!(
+            result,
+            Err(SolutionError::Syntax(ParseError::UnclosedParenthesis {
+                span: 0..1
+            }))
+        );
+    }
+
+    #[test]
+    fn check_solution_returns_error_for_too_many_numbers() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
+        app.player_mut().value_input = String::from("1 + 2 + 3 + 4 + 5 + 6 + 1");
+        app.target = 22;
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert_eq!(result, Err(SolutionError::TooManyNumbers(7)));
     }
 
     #[test]
@@ -375,14 +1784,161 @@ mod tests {
         // arrange
         let mut app = App::new();
         app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
-        app.value_input = String::from("1 + 2 + 3 + 4 + 5");
+        app.player_mut().value_input = String::from("1 + 2 + 3 + 4 + 5");
         app.target = 15;
 
         // act
         let result = app.check_solution();
 
         // assert
-        assert_eq!(result, Some(0));
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn submit_solution_finds_best_possible_answer() {
+        // arrange
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Playing;
+        app.selected_numbers = [Some(100), Some(75), Some(2), Some(4), Some(6), Some(8)];
+        app.target = 175;
+
+        // act
+        app.submit_solution();
+
+        // assert
+        assert!(matches!(
+            app.current_screen,
+            CurrentScreen::DisplayingResult
+        ));
+        let solution = app.solution.unwrap();
+        assert_eq!(solution.value, 175);
+        assert_eq!(solution.expression, "100 + 75");
+    }
+
+    #[test]
+    fn on_tick_submits_solution_when_time_runs_out() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(1), Some(2), Some(3), Some(4), Some(5), Some(6)];
+        app.target = 120;
+
+        // act
+        let result = app.on_tick(Duration::from_secs(29));
+
+        // assert
+        assert!(!result);
+        assert_eq!(app.time_remaining, DEFAULT_TIME_LIMIT);
+
+        // arrange
+        app.current_screen = CurrentScreen::Playing;
+
+        // act
+        let result = app.on_tick(Duration::from_secs(29));
+
+        // assert
+        assert!(!result);
+        assert!(matches!(app.current_screen, CurrentScreen::Playing));
+
+        // act
+        let result = app.on_tick(Duration::from_secs(2));
+
+        // assert
+        assert!(result);
+        assert!(app.time_remaining.is_zero());
+        assert!(matches!(
+            app.current_screen,
+            CurrentScreen::DisplayingResult
+        ));
+    }
+
+    #[test]
+    fn request_large_numbers_reveals_numbers_one_at_a_time() {
+        // arrange
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::PickingNumbers;
+
+        // act
+        app.request_large_numbers(2);
+        app.on_tick(Duration::from_millis(200));
+
+        // assert
+        assert_eq!(app.pending_picks.len(), 6);
+        assert!(app.selected_numbers.iter().all(Option::is_none));
+
+        // act
+        app.on_tick(Duration::from_millis(200));
+
+        // assert
+        assert_eq!(app.pending_picks.len(), 5);
+        assert!(app.selected_numbers[0].unwrap() >= 25);
+
+        // act
+        for _ in 0..5 {
+            app.on_tick(REVEAL_INTERVAL);
+        }
+
+        // assert
+        assert!(app.is_number_selection_complete());
+        let large_count = app
+            .selected_numbers
+            .iter()
+            .filter(|value| value.unwrap() >= 25)
+            .count();
+        assert_eq!(large_count, 2);
+    }
+
+    #[test]
+    fn pick_random_number_is_ignored_while_requested_numbers_are_revealed() {
+        // arrange
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::PickingNumbers;
+        app.request_large_numbers(2);
+
+        // act
+        app.pick_random_large_number();
+        app.pick_random_small_number();
+        for _ in 0..6 {
+            app.on_tick(REVEAL_INTERVAL);
+        }
+        app.pick_random_large_number();
+
+        // assert
+        assert!(app.is_number_selection_complete());
+        let large_count = app
+            .selected_numbers
+            .iter()
+            .filter(|value| value.unwrap() >= 25)
+            .count();
+        assert_eq!(large_count, 2);
+    }
+
+    #[test]
+    fn request_large_numbers_is_ignored_once_picking_has_started() {
+        // arrange
+        let mut app = App::new();
+        app.pick_random_small_number();
+
+        // act
+        app.request_large_numbers(4);
+
+        // assert
+        assert!(app.pending_picks.is_empty());
+    }
+
+    #[test]
+    fn redraw_unsolvable_target_picks_exactly_reachable_target() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(100), Some(1), Some(1), Some(1), Some(1), Some(1)];
+        app.target = 999;
+
+        // act
+        app.redraw_unsolvable_target();
+
+        // assert
+        assert_ne!(app.target, 999);
+        app.submit_solution();
+        assert_eq!(app.solution.unwrap().distance, 0);
     }
 
     #[test]
@@ -391,7 +1947,7 @@ mod tests {
         let input = "(10 *2) + 3 - 2 / 1";
 
         // act
-        let result = get_solution_numbers(input);
+        let result = parse(input).unwrap().numbers();
 
         // assert
         assert_eq!(result, [10, 2, 3, 2, 1]);
@@ -407,7 +1963,7 @@ mod tests {
         let result = check_solution_numbers(&input_numbers, &selected_numbers);
 
         // assert
-        assert!(result);
+        assert_eq!(result, Ok(()));
     }
 
     #[test]
@@ -420,7 +1976,7 @@ mod tests {
         let result = check_solution_numbers(&input_numbers, &selected_numbers);
 
         // assert
-        assert!(!result);
+        assert_eq!(result, Err(SolutionError::NumberUsedTooOften(2)));
     }
 
     #[test]
@@ -433,7 +1989,7 @@ mod tests {
         let result = check_solution_numbers(&input_numbers, &selected_numbers);
 
         // assert
-        assert!(!result);
+        assert_eq!(result, Err(SolutionError::NumberNotSelected(9)));
     }
 
     #[test]
@@ -442,22 +1998,123 @@ mod tests {
         let input = "(10 * 2) + 3 - 2 / 1";
 
         // act
-        let result = check_solution_calculation(input, 21);
+        let result = check_solution_calculation(&parse(input).unwrap(), 21, Rules::Strict);
 
         // assert
-        assert_eq!(result, Some(0));
+        assert_eq!(result, Ok(0));
     }
 
     #[test]
-    fn check_solution_calculation_returns_none_for_non_integer_division_result() {
+    fn check_solution_calculation_returns_error_for_non_integer_division_result() {
         // arrange
         let input = "(10 / 3) + 3 - 2 / 1";
 
         // act
-        let result = check_solution_calculation(input, 21);
+        let result = check_solution_calculation(&parse(input).unwrap(), 21, Rules::Strict);
 
         // assert
-        assert_eq!(result, None);
+        assert_eq!(
+            result,
+            Err(SolutionError::Calculation(
+                EvaluationError::NonIntegerDivision { span: 0..8 }
+            ))
+        );
+    }
+
+    #[test]
+    fn check_solution_calculation_returns_error_for_negative_intermediate_result() {
+        // arrange
+        let input = "2 - 10 + 9";
+
+        // act
+        let result = check_solution_calculation(&parse(input).unwrap(), 1, Rules::Strict);
+
+        // assert
+        assert_eq!(
+            result,
+            Err(SolutionError::Calculation(EvaluationError::NotPositive {
+                span: 0..6
+            }))
+        );
+
+        // act
+        let result = check_solution_calculation(&parse(input).unwrap(), 1, Rules::Lenient);
+
+        // assert
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn check_solution_calculation_returns_error_for_fractional_intermediate_result() {
+        // arrange
+        let input = "4 / 8 * 2";
+
+        // act
+        let result = check_solution_calculation(&parse(input).unwrap(), 1, Rules::Strict);
+
+        // assert
+        assert_eq!(
+            result,
+            Err(SolutionError::Calculation(
+                EvaluationError::NonIntegerDivision { span: 0..5 }
+            ))
+        );
+
+        // act
+        let result = check_solution_calculation(&parse(input).unwrap(), 1, Rules::Lenient);
+
+        // assert
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn check_solution_calculation_returns_error_for_non_integer_result_under_lenient_rules() {
+        // arrange
+        let input = "(10 / 3) + 3 - 2 / 1";
+
+        // act
+        let result = check_solution_calculation(&parse(input).unwrap(), 21, Rules::Lenient);
+
+        // assert
+        assert_eq!(
+            result,
+            Err(SolutionError::Calculation(
+                EvaluationError::NonIntegerResult
+            ))
+        );
+    }
+
+    #[test]
+    fn check_solution_applies_selected_rules() {
+        // arrange
+        let mut app = App::new();
+        app.selected_numbers = [Some(2), Some(10), Some(9), Some(4), Some(8), Some(75)];
+        app.player_mut().value_input = String::from("2 - 10 + 9");
+        app.target = 101;
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert!(matches!(result, Err(SolutionError::Calculation(_))));
+
+        // arrange
+        app.settings.rules = Rules::Lenient;
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert_eq!(result, Ok(100));
+
+        // arrange
+        app.settings.input_mode = InputMode::Calculator;
+
+        // act
+        let result = app.check_solution();
+
+        // assert
+        assert!(matches!(result, Err(SolutionError::Calculation(_))));
     }
 
     #[test]
@@ -466,10 +2123,10 @@ mod tests {
         let input = "(10 * 2) + 3 - 2 / 1";
 
         // act
-        let result = check_solution_calculation(input, 20);
+        let result = check_solution_calculation(&parse(input).unwrap(), 20, Rules::Strict);
 
         // assert
-        assert_eq!(result, Some(1));
+        assert_eq!(result, Ok(1));
     }
 
     #[test]
@@ -478,21 +2135,21 @@ mod tests {
         let input = "(10 * 2) + 3 - 2 / 1";
 
         // act
-        let result = check_solution_calculation(input, 22);
+        let result = check_solution_calculation(&parse(input).unwrap(), 22, Rules::Strict);
 
         // assert
-        assert_eq!(result, Some(1));
+        assert_eq!(result, Ok(1));
     }
 
     #[test]
-    fn check_solution_calculation_parses_invalid_input() {
+    fn parse_rejects_solution_with_unclosed_parenthesis() {
         // arrange
         let input = "(10 * 2 + 3 - 2 / 1";
 
         // act
-        let result = check_solution_calculation(input, 21);
+        let result = parse(input);
 
         // assert
-        assert_eq!(result, None);
+        assert_eq!(result, Err(ParseError::UnclosedParenthesis { span: 0..1 }));
     }
 }
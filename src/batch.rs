@@ -0,0 +1,340 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::{App, Settings},
+    expression::Rules,
+    puzzle_code::Puzzle,
+    solver,
+};
+
+/// Format for the results of solving a batch of puzzles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Best solution found for a puzzle, and how long finding it took
+#[derive(Clone, Debug, PartialEq)]
+pub struct PuzzleSolution {
+    pub best_value: u32,
+    pub distance: u32,
+    /// Example solution reaching `best_value`
+    pub expression: String,
+    /// Distinct exact solutions, as listed by [`solver::exact_solutions`], which is zero when the
+    /// target cannot be hit
+    pub solution_count: usize,
+    /// Time taken to find the best solution, not counting the exact solutions
+    pub solve_time: Duration,
+}
+
+/// Outcome for one line of input
+#[derive(Clone, Debug, PartialEq)]
+pub struct PuzzleResult {
+    /// Line number in the input, counting from one
+    pub line: usize,
+    pub numbers: Vec<u32>,
+    pub target: Option<u32>,
+    pub solution: Result<PuzzleSolution, String>,
+}
+
+/// Reads six numbers and a target from a line such as `25 50 3 6 7 9 812`, separated by spaces
+/// or commas
+fn parse_line(line: &str) -> Result<([u32; 6], u32), String> {
+    let values = line
+        .split(|character: char| character == ',' || character.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("`{value}` is not a whole number"))
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+    let [numbers @ .., target] = values.as_slice() else {
+        return Err(String::from("expected six numbers and a target"));
+    };
+    let numbers: [u32; 6] = numbers
+        .try_into()
+        .map_err(|_| String::from("expected six numbers and a target"))?;
+    Ok((numbers, *target))
+}
+
+/// Solves `puzzle`, checking the example solution with the same rules as a game would
+fn solve_puzzle(puzzle: &Puzzle, rules: Rules) -> Result<PuzzleSolution, String> {
+    let start = Instant::now();
+    let best = solver::solve(&puzzle.numbers, puzzle.target);
+    let solve_time = start.elapsed();
+    let best = best.ok_or_else(|| String::from("no solution found"))?;
+    let solution_count = if best.distance == 0 {
+        solver::exact_solutions(&puzzle.numbers, puzzle.target).len()
+    } else {
+        0
+    };
+
+    let settings = Settings {
+        rules,
+        ..Settings::default()
+    };
+    let distance = App::from_puzzle(settings, puzzle)
+        .check_input(&best.expression)
+        .map_err(|error| format!("solver found an invalid solution: {error}"))?;
+    if distance != best.distance {
+        return Err(format!(
+            "solver found {} for `{}`, but it comes to {distance} away",
+            best.value, best.expression
+        ));
+    }
+
+    Ok(PuzzleSolution {
+        best_value: best.value,
+        distance: best.distance,
+        expression: best.expression,
+        solution_count,
+        solve_time,
+    })
+}
+
+/// Solves each puzzle in `input`, one per line.  Blank lines and lines starting with `#` are
+/// skipped, and lines which are not valid puzzles give a result with an error, along with any
+/// numbers and target read from them.
+pub fn solve_puzzles(input: &str, rules: Rules) -> Vec<PuzzleResult> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(index, line)| match parse_line(line) {
+            Ok((numbers, target)) => PuzzleResult {
+                line: index + 1,
+                numbers: numbers.to_vec(),
+                target: Some(target),
+                solution: Puzzle::new(numbers, target)
+                    .map_err(|error| error.to_string())
+                    .and_then(|puzzle| solve_puzzle(&puzzle, rules)),
+            },
+            Err(error) => PuzzleResult {
+                line: index + 1,
+                numbers: Vec::new(),
+                target: None,
+                solution: Err(error),
+            },
+        })
+        .collect()
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            character if character.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", u32::from(character));
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+fn numbers_text(numbers: &[u32], separator: &str) -> String {
+    numbers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn to_json(results: &[PuzzleResult]) -> String {
+    let objects: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let target = result
+                .target
+                .map_or_else(|| String::from("null"), |value| value.to_string());
+            let fields = match &result.solution {
+                Ok(solution) => format!(
+                    "\"best_value\": {}, \"distance\": {}, \"expression\": {}, \
+                     \"solution_count\": {}, \"solve_time_ms\": {}, \"error\": null",
+                    solution.best_value,
+                    solution.distance,
+                    json_string(&solution.expression),
+                    solution.solution_count,
+                    milliseconds(solution.solve_time)
+                ),
+                Err(error) => format!(
+                    "\"best_value\": null, \"distance\": null, \"expression\": null, \
+                     \"solution_count\": null, \"solve_time_ms\": null, \"error\": {}",
+                    json_string(error)
+                ),
+            };
+            format!(
+                "  {{\"line\": {}, \"numbers\": [{}], \"target\": {target}, {fields}}}",
+                result.line,
+                numbers_text(&result.numbers, ", ")
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        String::from("[]\n")
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+fn to_csv(results: &[PuzzleResult]) -> String {
+    let mut csv = String::from(
+        "line,numbers,target,best_value,distance,expression,solution_count,solve_time_ms,error\n",
+    );
+    for result in results {
+        let target = result.target.map(|value| value.to_string());
+        let solution_fields = match &result.solution {
+            Ok(solution) => [
+                solution.best_value.to_string(),
+                solution.distance.to_string(),
+                csv_field(&solution.expression),
+                solution.solution_count.to_string(),
+                milliseconds(solution.solve_time),
+                String::new(),
+            ],
+            Err(error) => [
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                csv_field(error),
+            ],
+        };
+        let _ = writeln!(
+            csv,
+            "{},{},{},{}",
+            result.line,
+            numbers_text(&result.numbers, " "),
+            target.unwrap_or_default(),
+            solution_fields.join(",")
+        );
+    }
+    csv
+}
+
+/// Results written out in `format`, one puzzle per record
+pub fn format_results(results: &[PuzzleResult], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => to_json(results),
+        OutputFormat::Csv => to_csv(results),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_results, solve_puzzles, OutputFormat, PuzzleResult, PuzzleSolution};
+    use crate::expression::Rules;
+    use std::time::Duration;
+
+    fn results() -> Vec<PuzzleResult> {
+        vec![
+            PuzzleResult {
+                line: 1,
+                numbers: vec![25, 50, 3, 6, 7, 9],
+                target: Some(812),
+                solution: Ok(PuzzleSolution {
+                    best_value: 812,
+                    distance: 0,
+                    expression: String::from("(25 * 3 + 50 - 9) * 7"),
+                    solution_count: 44,
+                    solve_time: Duration::from_micros(12_500),
+                }),
+            },
+            PuzzleResult {
+                line: 3,
+                numbers: Vec::new(),
+                target: None,
+                solution: Err(String::from("`x` is not a whole number, \"quoted\"")),
+            },
+        ]
+    }
+
+    #[test]
+    fn solve_puzzles_solves_valid_lines_and_reports_invalid_ones() {
+        // arrange
+        let input = "# numbers, then target\n100, 75, 50, 25, 6, 3, 952\n\n1 1 1 1 1 1 999\n7 7 7 1 2 3 500\n";
+
+        // act
+        let results = solve_puzzles(input, Rules::Strict);
+
+        // assert
+        assert_eq!(results.len(), 3);
+        let solution = results[0].solution.as_ref().unwrap();
+        assert_eq!(results[0].line, 2);
+        assert_eq!(results[0].target, Some(952));
+        assert_eq!((solution.best_value, solution.distance), (952, 0));
+        assert!(solution.solution_count > 0);
+        assert_eq!(
+            results[1].solution,
+            Err(String::from("1 appears more times than it can be picked"))
+        );
+        assert_eq!(
+            (results[1].numbers.as_slice(), results[1].target),
+            ([1, 1, 1, 1, 1, 1].as_slice(), Some(999))
+        );
+        assert_eq!(results[2].line, 5);
+        assert!(results[2].solution.is_err());
+        assert_eq!(results[2].target, Some(500));
+    }
+
+    #[test]
+    fn format_results_writes_json() {
+        // act
+        let json = format_results(&results(), OutputFormat::Json);
+
+        // assert
+        assert_eq!(
+            json,
+            "[\n  {\"line\": 1, \"numbers\": [25, 50, 3, 6, 7, 9], \"target\": 812, \
+             \"best_value\": 812, \"distance\": 0, \"expression\": \"(25 * 3 + 50 - 9) * 7\", \
+             \"solution_count\": 44, \"solve_time_ms\": 12.500, \"error\": null},\n  \
+             {\"line\": 3, \"numbers\": [], \"target\": null, \"best_value\": null, \
+             \"distance\": null, \"expression\": null, \"solution_count\": null, \
+             \"solve_time_ms\": null, \"error\": \"`x` is not a whole number, \\\"quoted\\\"\"}\n]\n"
+        );
+        assert_eq!(format_results(&[], OutputFormat::Json), "[]\n");
+    }
+
+    #[test]
+    fn format_results_writes_csv() {
+        // act
+        let csv = format_results(&results(), OutputFormat::Csv);
+
+        // assert
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "line,numbers,target,best_value,distance,expression,solution_count,solve_time_ms,error",
+                "1,25 50 3 6 7 9,812,812,0,(25 * 3 + 50 - 9) * 7,44,12.500,",
+                "3,,,,,,,,\"`x` is not a whole number, \"\"quoted\"\"\""
+            ]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::{fmt, ops::RangeInclusive};
+
+/// How hard the computer opponent looks for a solution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BotStrength {
+    /// Only finds simple answers, with two or three operations
+    #[default]
+    Beginner,
+
+    /// Finds answers with up to four operations
+    Intermediate,
+
+    /// Always finds the best possible answer
+    Expert,
+}
+
+impl BotStrength {
+    /// Range of operation counts the bot may limit itself to, picked afresh each round
+    pub fn max_steps(self) -> RangeInclusive<usize> {
+        match self {
+            BotStrength::Beginner => 2..=3,
+            BotStrength::Intermediate => 3..=4,
+            BotStrength::Expert => 5..=5,
+        }
+    }
+
+    /// Range of word lengths the bot may limit itself to in a letters round, picked afresh each
+    /// round
+    pub fn max_word_length(self) -> RangeInclusive<usize> {
+        match self {
+            BotStrength::Beginner => 4..=5,
+            BotStrength::Intermediate => 6..=7,
+            BotStrength::Expert => 9..=9,
+        }
+    }
+
+    /// Chance of the bot solving a conundrum in time
+    pub fn conundrum_odds(self) -> f64 {
+        match self {
+            BotStrength::Beginner => 0.25,
+            BotStrength::Intermediate => 0.5,
+            BotStrength::Expert => 0.9,
+        }
+    }
+}
+
+impl fmt::Display for BotStrength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotStrength::Beginner => write!(f, "beginner"),
+            BotStrength::Intermediate => write!(f, "intermediate"),
+            BotStrength::Expert => write!(f, "expert"),
+        }
+    }
+}
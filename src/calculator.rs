@@ -0,0 +1,244 @@
+use std::fmt;
+
+use crate::{expression::Operator, solver::Step};
+
+/// How players enter their solution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputMode {
+    /// Typing a whole infix expression
+    #[default]
+    Expression,
+
+    /// Combining two numbers at a time, as on the show’s whiteboard
+    Calculator,
+}
+
+impl fmt::Display for InputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputMode::Expression => write!(f, "type a whole expression"),
+            InputMode::Calculator => write!(f, "step by step, two numbers at a time"),
+        }
+    }
+}
+
+/// Number available to the calculator, along with the working which made it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tile {
+    pub value: u32,
+    expression: String,
+}
+
+impl Tile {
+    fn new(value: u32) -> Tile {
+        Tile {
+            value,
+            expression: value.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CalculatorError {
+    SameTile,
+    InvalidOperation {
+        left: u32,
+        operator: Operator,
+        right: u32,
+    },
+}
+
+impl fmt::Display for CalculatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalculatorError::SameTile => write!(f, "Pick two different numbers"),
+            CalculatorError::InvalidOperation {
+                left,
+                operator,
+                right,
+            } => write!(
+                f,
+                "{left} {operator} {right} does not give a positive whole number"
+            ),
+        }
+    }
+}
+
+/// Step-by-step working, where each operation replaces two tiles with its result
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Calculator {
+    pub tiles: Vec<Tile>,
+    pub steps: Vec<Step>,
+    /// Tiles before each step, so steps can be undone
+    history: Vec<Vec<Tile>>,
+    /// Index of the first tile picked for the next operation
+    pub left: Option<usize>,
+    pub operator: Option<Operator>,
+    /// Problem with the last operation attempted
+    pub error: Option<CalculatorError>,
+}
+
+impl Calculator {
+    pub fn new(numbers: &[u32]) -> Calculator {
+        Calculator {
+            tiles: numbers.iter().copied().map(Tile::new).collect(),
+            ..Calculator::default()
+        }
+    }
+
+    /// Picks the tile at `index`, either as the first number of the next operation or, once an
+    /// operator is chosen, as the second number, which completes the step.
+    pub fn select_tile(&mut self, index: usize) {
+        if index >= self.tiles.len() {
+            return;
+        }
+        match (self.left, self.operator) {
+            (Some(left), Some(_)) if left == index => self.error = Some(CalculatorError::SameTile),
+            (Some(left), Some(operator)) => self.combine(left, operator, index),
+            _ => {
+                self.left = Some(index);
+                self.error = None;
+            }
+        }
+    }
+
+    pub fn select_operator(&mut self, operator: Operator) {
+        if self.left.is_some() {
+            self.operator = Some(operator);
+        }
+    }
+
+    fn combine(&mut self, left_index: usize, operator: Operator, right_index: usize) {
+        let (left, right) = (&self.tiles[left_index], &self.tiles[right_index]);
+        let Some(result) = operator.apply(left.value, right.value) else {
+            self.error = Some(CalculatorError::InvalidOperation {
+                left: left.value,
+                operator,
+                right: right.value,
+            });
+            return;
+        };
+        let tile = Tile {
+            value: result,
+            expression: format!("({} {operator} {})", left.expression, right.expression),
+        };
+        self.steps.push(Step {
+            left: left.value,
+            operator,
+            right: right.value,
+            result,
+        });
+        self.history.push(self.tiles.clone());
+
+        // remove the later tile first, so the earlier index stays valid
+        self.tiles.remove(left_index.max(right_index));
+        self.tiles.remove(left_index.min(right_index));
+        self.tiles.push(tile);
+        self.left = None;
+        self.operator = None;
+        self.error = None;
+    }
+
+    /// Backs out of the operation being entered or, if none is in progress, undoes the last step.
+    pub fn undo(&mut self) {
+        self.error = None;
+        if self.operator.take().is_some() || self.left.take().is_some() {
+            return;
+        }
+        if let Some(tiles) = self.history.pop() {
+            self.tiles = tiles;
+            self.steps.pop();
+        }
+    }
+
+    /// Working for the latest tile made, to be scored as the answer.  Before any steps, a picked
+    /// tile can be the answer on its own.
+    pub fn answer(&self) -> String {
+        let tile = if self.steps.is_empty() {
+            self.left.map(|index| &self.tiles[index])
+        } else {
+            self.tiles.last()
+        };
+        tile.map(|value| {
+            value
+                .expression
+                .strip_prefix('(')
+                .and_then(|expression| expression.strip_suffix(')'))
+                .unwrap_or(&value.expression)
+                .to_string()
+        })
+        .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calculator, CalculatorError};
+    use crate::expression::Operator;
+
+    #[test]
+    fn calculator_replaces_combined_tiles_with_result() {
+        // arrange
+        let mut calculator = Calculator::new(&[25, 50, 3, 6, 7, 9]);
+
+        // act
+        calculator.select_tile(5);
+        calculator.select_operator(Operator::Add);
+        calculator.select_tile(4);
+        calculator.select_tile(1);
+        calculator.select_operator(Operator::Multiply);
+        calculator.select_tile(4);
+
+        // assert
+        let values: Vec<u32> = calculator.tiles.iter().map(|value| value.value).collect();
+        assert_eq!(values, [25, 3, 6, 800]);
+        assert_eq!(calculator.steps.len(), 2);
+        assert_eq!(calculator.steps[1].to_string(), "50 * 16 = 800");
+        assert_eq!(calculator.answer(), "50 * (9 + 7)");
+    }
+
+    #[test]
+    fn calculator_rejects_invalid_operations() {
+        // arrange
+        let mut calculator = Calculator::new(&[25, 50, 3, 6, 7, 9]);
+
+        // act
+        calculator.select_tile(2);
+        calculator.select_operator(Operator::Subtract);
+        calculator.select_tile(3);
+        let invalid_operation = calculator.error.clone();
+        calculator.select_tile(2);
+
+        // assert
+        assert_eq!(
+            invalid_operation,
+            Some(CalculatorError::InvalidOperation {
+                left: 3,
+                operator: Operator::Subtract,
+                right: 6
+            })
+        );
+        assert_eq!(calculator.error, Some(CalculatorError::SameTile));
+        assert_eq!(calculator.tiles.len(), 6);
+    }
+
+    #[test]
+    fn calculator_undoes_steps() {
+        // arrange
+        let mut calculator = Calculator::new(&[25, 50, 3, 6, 7, 9]);
+        calculator.select_tile(0);
+        calculator.select_operator(Operator::Multiply);
+        calculator.select_tile(2);
+        calculator.select_tile(0);
+
+        // act
+        calculator.undo();
+        let after_first_undo = (calculator.left, calculator.steps.len());
+        calculator.undo();
+
+        // assert
+        assert_eq!(after_first_undo, (None, 1));
+        assert_eq!(calculator, Calculator::new(&[25, 50, 3, 6, 7, 9]));
+        assert_eq!(calculator.answer(), "");
+    }
+}
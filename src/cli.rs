@@ -0,0 +1,556 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::{
+    app::{App, Settings},
+    batch::OutputFormat,
+    episode::{self, RoundKind},
+    expression::Rules,
+    history::Statistics,
+    puzzle_code::Puzzle,
+    solver,
+};
+
+/// Exact solutions listed by `solve`, unless `--all` is given
+const SOLUTIONS_SHOWN: usize = 10;
+
+pub const USAGE: &str = "Usage: countdown-numbers [COMMAND] [OPTIONS]
+
+Commands:
+  play     Play in the terminal (default)
+             --seed <number>        reproducible game
+             --daily                today’s daily puzzle
+             --puzzle <code>        shared puzzle
+             --time-limit <secs>    time allowed for each solution
+             --rules <strict|lenient>
+             --rounds <sequence>    rounds for a full game, such as llnllnc for letters,
+                                    letters, numbers, letters, letters, numbers, conundrum
+             --mute                 no sound effects
+  solve    Print solutions: solve 25 50 3 6 7 9 --target 812 [--all]
+  check    Check a solution: check 25 50 3 6 7 9 --target 812 \"50 * 16 + 12\" [--rules <rules>]
+  batch    Solve puzzles, one per line of six numbers and a target, from a file or stdin:
+             batch [FILE|-] [--format <json|csv>] [--rules <rules>]
+  stats    Print statistics from your score history
+  help     Print this message";
+
+/// Options for starting the terminal game
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PlayOptions {
+    pub seed: Option<u64>,
+    pub daily: bool,
+    pub puzzle: Option<Puzzle>,
+    pub time_limit: Option<Duration>,
+    pub rules: Option<Rules>,
+    /// Rounds for a full game, in order
+    pub rounds: Option<Vec<RoundKind>>,
+    pub mute: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Play(PlayOptions),
+    Solve {
+        numbers: Vec<u32>,
+        target: u32,
+        all: bool,
+    },
+    Check {
+        puzzle: Puzzle,
+        expression: String,
+        rules: Rules,
+    },
+    Batch {
+        /// File to read puzzles from, or `None` for stdin
+        input: Option<String>,
+        format: OutputFormat,
+        rules: Rules,
+    },
+    Stats,
+    Help,
+}
+
+/// Value following `flag`, parsed, with `description` naming what was expected in errors
+fn flag_value<I: Iterator<Item = String>, T: std::str::FromStr>(
+    args: &mut I,
+    flag: &str,
+    description: &str,
+) -> Result<T, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("`{flag}` needs a value"))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value `{value}` for `{flag}`, expected {description}"))
+}
+
+fn parse_rules(value: &str) -> Result<Rules, String> {
+    match value {
+        "strict" => Ok(Rules::Strict),
+        "lenient" => Ok(Rules::Lenient),
+        other => Err(format!(
+            "Unknown rules `{other}`, expected `strict` or `lenient`"
+        )),
+    }
+}
+
+fn parse_batch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut input = None;
+    let mut format = OutputFormat::default();
+    let mut rules = Rules::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value: String = flag_value(&mut args, "--format", "`json` or `csv`")?;
+                format = match value.as_str() {
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    other => {
+                        return Err(format!(
+                            "Unknown format `{other}`, expected `json` or `csv`"
+                        ))
+                    }
+                };
+            }
+            "--rules" => {
+                let value: String = flag_value(&mut args, "--rules", "`strict` or `lenient`")?;
+                rules = parse_rules(&value)?;
+            }
+            "-" if input.is_none() => input = Some(None),
+            other if !other.starts_with("--") && input.is_none() => {
+                input = Some(Some(arg));
+            }
+            other => return Err(format!("Unknown argument `{other}`")),
+        }
+    }
+    Ok(Command::Batch {
+        input: input.flatten(),
+        format,
+        rules,
+    })
+}
+
+fn parse_play<I: Iterator<Item = String>>(mut args: I) -> Result<PlayOptions, String> {
+    let mut options = PlayOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => options.seed = Some(flag_value(&mut args, "--seed", "a whole number")?),
+            "--daily" => options.daily = true,
+            "--puzzle" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| String::from("`--puzzle` needs a code"))?;
+                options.puzzle =
+                    Some(Puzzle::from_code(&value).map_err(|error| error.to_string())?);
+            }
+            "--time-limit" => {
+                let seconds: u64 = flag_value(&mut args, "--time-limit", "a number of seconds")?;
+                if seconds == 0 {
+                    return Err(String::from("`--time-limit` must be at least one second"));
+                }
+                options.time_limit = Some(Duration::from_secs(seconds));
+            }
+            "--rules" => {
+                let value: String = flag_value(&mut args, "--rules", "`strict` or `lenient`")?;
+                options.rules = Some(parse_rules(&value)?);
+            }
+            "--rounds" => {
+                let value: String =
+                    flag_value(&mut args, "--rounds", "a round sequence such as `llnllnc`")?;
+                options.rounds = Some(episode::parse_rounds(&value)?);
+            }
+            "--mute" => options.mute = true,
+            other => return Err(format!("Unknown argument `{other}`")),
+        }
+    }
+    Ok(options)
+}
+
+/// Numbers, target and any words left over, along with `--all` for `solve` and `--rules` for
+/// `check`
+#[derive(Default)]
+struct PuzzleArguments {
+    numbers: Vec<u32>,
+    target: Option<u32>,
+    words: Vec<String>,
+    all: bool,
+    rules: Rules,
+}
+
+fn parse_puzzle_arguments<I: Iterator<Item = String>>(
+    command: &str,
+    mut args: I,
+) -> Result<PuzzleArguments, String> {
+    let mut arguments = PuzzleArguments::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--target" => {
+                arguments.target = Some(flag_value(&mut args, "--target", "a whole number")?);
+            }
+            "--all" if command == "solve" => arguments.all = true,
+            "--rules" if command == "check" => {
+                let value: String = flag_value(&mut args, "--rules", "`strict` or `lenient`")?;
+                arguments.rules = parse_rules(&value)?;
+            }
+            other @ ("--all" | "--rules") => {
+                return Err(format!("`{command}` does not take `{other}`\n\n{USAGE}"));
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown argument `{other}`"));
+            }
+            other => match other.parse() {
+                Ok(value) if arguments.numbers.len() < 6 && arguments.words.is_empty() => {
+                    arguments.numbers.push(value);
+                }
+                _ => arguments.words.push(arg),
+            },
+        }
+    }
+    Ok(arguments)
+}
+
+/// Parses command line arguments into a command.  Running with no command, or with only the
+/// options for `play`, starts the terminal game, as before subcommands were added.
+pub fn parse_arguments<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    let command = match args.peek().map(String::as_str) {
+        None => return Ok(Command::Play(PlayOptions::default())),
+        Some(value) if value.starts_with("--") && value != "--help" => {
+            return parse_play(args).map(Command::Play);
+        }
+        Some(_) => args.next().unwrap_or_default(),
+    };
+
+    match command.as_str() {
+        "play" => parse_play(args).map(Command::Play),
+        "solve" => {
+            let arguments = parse_puzzle_arguments(&command, args)?;
+            if let Some(word) = arguments.words.first() {
+                return Err(format!("Unexpected argument `{word}`"));
+            }
+            if arguments.numbers.is_empty() || arguments.numbers.contains(&0) {
+                return Err(String::from("`solve` needs one to six positive numbers"));
+            }
+            Ok(Command::Solve {
+                numbers: arguments.numbers,
+                target: arguments
+                    .target
+                    .ok_or_else(|| String::from("`solve` needs a `--target`"))?,
+                all: arguments.all,
+            })
+        }
+        "check" => {
+            let arguments = parse_puzzle_arguments(&command, args)?;
+            let numbers: [u32; 6] = arguments
+                .numbers
+                .try_into()
+                .map_err(|_| String::from("`check` needs six numbers"))?;
+            let target = arguments
+                .target
+                .ok_or_else(|| String::from("`check` needs a `--target`"))?;
+            Ok(Command::Check {
+                puzzle: Puzzle::new(numbers, target).map_err(|error| error.to_string())?,
+                expression: arguments.words.join(" "),
+                rules: arguments.rules,
+            })
+        }
+        "batch" => parse_batch(args),
+        "stats" => match args.next() {
+            Some(other) => Err(format!("Unknown argument `{other}`")),
+            None => Ok(Command::Stats),
+        },
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("Unknown command `{other}`\n\n{USAGE}")),
+    }
+}
+
+/// Best solution for `numbers` and `target`, followed by the exact solutions, for `solve`
+pub fn solve_report(numbers: &[u32], target: u32, all: bool) -> String {
+    let Some(best) = solver::solve(numbers, target) else {
+        return String::from("No solutions found\n");
+    };
+    let mut report = if best.distance == 0 {
+        format!("Best: {} = {} (exact)\n", best.expression, best.value)
+    } else {
+        format!(
+            "Best: {} = {} ({} away)\n",
+            best.expression, best.value, best.distance
+        )
+    };
+
+    let solutions = solver::exact_solutions(numbers, target);
+    let shown = if all {
+        solutions.len()
+    } else {
+        SOLUTIONS_SHOWN
+    };
+    match solutions.len() {
+        0 => {}
+        1 => report.push_str("1 exact solution:\n"),
+        count if count > shown => {
+            let _ = writeln!(report, "{count} exact solutions, the first {shown}:");
+        }
+        count => {
+            let _ = writeln!(report, "{count} exact solutions:");
+        }
+    }
+    for solution in solutions.iter().take(shown) {
+        let _ = writeln!(report, "  {solution}");
+    }
+    report
+}
+
+/// Result of checking `expression` against a puzzle, for `check`.  Invalid solutions are
+/// returned as an error, so the command can exit with a failure status.
+pub fn check_report(puzzle: &Puzzle, expression: &str, rules: Rules) -> Result<String, String> {
+    let settings = Settings {
+        rules,
+        ..Settings::default()
+    };
+    let app = App::from_puzzle(settings, puzzle);
+    match app.check_input(expression) {
+        Ok(0) => Ok(format!(
+            "{} = {}, hitting the target\n",
+            expression.trim(),
+            puzzle.target
+        )),
+        Ok(distance) => Ok(format!(
+            "{} is {distance} away from the target {}\n",
+            expression.trim(),
+            puzzle.target
+        )),
+        Err(error) => Err(format!("Invalid solution: {error}")),
+    }
+}
+
+/// Statistics from the score history, for `stats`
+pub fn stats_report(statistics: &Statistics) -> String {
+    if statistics.rounds_played == 0 {
+        return String::from("No rounds played yet.\n");
+    }
+    let exact_hit_rate = statistics
+        .exact_hit_rate()
+        .map(|value| format!("{value:.0}%"))
+        .unwrap_or_default();
+    let average_distance = statistics
+        .average_distance
+        .map_or_else(|| String::from("—"), |value| format!("{value:.1}"));
+    format!(
+        "Rounds played: {}\nExact hits: {exact_hit_rate}\nAverage distance from target: \
+         {average_distance}\nCurrent streak of exact hits: {}\n",
+        statistics.rounds_played, statistics.current_streak
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_report, parse_arguments, solve_report, Command, PlayOptions};
+    use crate::{batch::OutputFormat, episode::RoundKind, expression::Rules, puzzle_code::Puzzle};
+    use std::time::Duration;
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|value| String::from(*value))
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parse_arguments_reads_seed_argument() {
+        // act
+        let result = parse_arguments(args(&["--seed", "42"]));
+
+        // assert
+        assert_eq!(
+            result,
+            Ok(Command::Play(PlayOptions {
+                seed: Some(42),
+                ..PlayOptions::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_arguments_reads_daily_flag() {
+        // act
+        let result = parse_arguments(args(&["--daily"]));
+
+        // assert
+        assert_eq!(
+            result,
+            Ok(Command::Play(PlayOptions {
+                daily: true,
+                ..PlayOptions::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_arguments_reads_puzzle_code() {
+        // arrange
+        let puzzle = Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap();
+        let code = puzzle.code();
+
+        // act
+        let result = parse_arguments(args(&["--puzzle", &code]));
+
+        // assert
+        assert_eq!(
+            result,
+            Ok(Command::Play(PlayOptions {
+                puzzle: Some(puzzle),
+                ..PlayOptions::default()
+            }))
+        );
+        assert!(parse_arguments(args(&["--puzzle", "ABC"])).is_err());
+    }
+
+    #[test]
+    fn parse_arguments_rejects_invalid_arguments() {
+        // act
+        let results = (
+            parse_arguments(args(&["--seed"])),
+            parse_arguments(args(&["--seed", "abc"])),
+            parse_arguments(args(&["--colour"])),
+            parse_arguments(std::iter::empty()),
+        );
+
+        // assert
+        assert!(results.0.is_err());
+        assert!(results.1.is_err());
+        assert!(results.2.is_err());
+        assert_eq!(results.3, Ok(Command::Play(PlayOptions::default())));
+    }
+
+    #[test]
+    fn parse_arguments_reads_subcommands() {
+        // act
+        let results = (
+            parse_arguments(args(&[
+                "play",
+                "--time-limit",
+                "45",
+                "--rules",
+                "lenient",
+                "--rounds",
+                "LNC",
+                "--mute",
+            ])),
+            parse_arguments(args(&["solve", "25", "50", "3", "--target", "812"])),
+            parse_arguments(args(&[
+                "check", "25", "50", "3", "6", "7", "9", "--target", "812", "50", "*", "16", "+",
+                "12",
+            ])),
+            parse_arguments(args(&["stats"])),
+            parse_arguments(args(&["check", "25", "50", "--target", "812", "75"])),
+            parse_arguments(args(&["solve", "25", "50"])),
+            parse_arguments(args(&["batch", "puzzles.txt", "--format", "csv"])),
+            parse_arguments(args(&["batch", "--format", "xml"])),
+            parse_arguments(args(&["play", "--rounds", "lnx"])),
+            parse_arguments(args(&["solve", "25", "50", "--target", "75", "--all"])),
+        );
+
+        // assert
+        assert_eq!(
+            results.0,
+            Ok(Command::Play(PlayOptions {
+                time_limit: Some(Duration::from_secs(45)),
+                rules: Some(Rules::Lenient),
+                rounds: Some(vec![
+                    RoundKind::Letters,
+                    RoundKind::Numbers,
+                    RoundKind::Conundrum
+                ]),
+                mute: true,
+                ..PlayOptions::default()
+            }))
+        );
+        assert_eq!(
+            results.1,
+            Ok(Command::Solve {
+                numbers: vec![25, 50, 3],
+                target: 812,
+                all: false
+            })
+        );
+        assert_eq!(
+            results.2,
+            Ok(Command::Check {
+                puzzle: Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap(),
+                expression: String::from("50 * 16 + 12"),
+                rules: Rules::Strict
+            })
+        );
+        assert_eq!(results.3, Ok(Command::Stats));
+        assert!(results.4.is_err());
+        assert!(results.5.is_err());
+        assert_eq!(
+            results.6,
+            Ok(Command::Batch {
+                input: Some(String::from("puzzles.txt")),
+                format: OutputFormat::Csv,
+                rules: Rules::Strict
+            })
+        );
+        assert!(results.7.is_err());
+        assert!(results.8.is_err());
+        assert!(matches!(results.9, Ok(Command::Solve { all: true, .. })));
+    }
+
+    #[test]
+    fn parse_arguments_rejects_options_a_command_does_not_take() {
+        // act
+        let results = (
+            parse_arguments(args(&[
+                "solve", "25", "50", "--target", "75", "--rules", "lenient",
+            ])),
+            parse_arguments(args(&[
+                "check", "25", "50", "3", "6", "7", "9", "--target", "812", "--all", "50", "*",
+                "16",
+            ])),
+        );
+
+        // assert
+        assert!(results
+            .0
+            .is_err_and(|error| error.starts_with("`solve` does not take `--rules`")));
+        assert!(results
+            .1
+            .is_err_and(|error| error.starts_with("`check` does not take `--all`")));
+    }
+
+    #[test]
+    fn solve_report_shows_best_and_exact_solutions() {
+        // act
+        let exact = solve_report(&[1, 2, 3], 6, false);
+        let closest = solve_report(&[2, 3], 100, false);
+
+        // assert
+        assert!(exact.starts_with("Best: "));
+        assert!(exact.contains("(exact)"));
+        assert!(exact.contains("exact solutions:\n  "));
+        assert_eq!(closest, "Best: 3 * 2 = 6 (94 away)\n");
+    }
+
+    #[test]
+    fn check_report_uses_round_validation() {
+        // arrange
+        let puzzle = Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap();
+
+        // act
+        let results = (
+            check_report(&puzzle, "50 * (7 + 9) + 6 + 3 + 3", Rules::Strict),
+            check_report(&puzzle, "50 * (7 + 9) + 6 + 3", Rules::Strict),
+            check_report(&puzzle, "50 * 16 + 12", Rules::Strict),
+        );
+
+        // assert
+        assert!(results.0.is_err());
+        assert_eq!(
+            results.1,
+            Ok(String::from(
+                "50 * (7 + 9) + 6 + 3 is 3 away from the target 812\n"
+            ))
+        );
+        assert!(results.2.is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use rand::{
+    prelude::{IndexedRandom, SliceRandom},
+    Rng,
+};
+
+use crate::letters::{self, LETTER_COUNT};
+
+/// Lower case nine-letter words, one per line, which conundrums are made from
+const CONUNDRUM_LIST: &str = include_str!("../assets/conundrums.txt");
+
+/// Points for solving the conundrum first
+pub const CONUNDRUM_POINTS: u32 = 10;
+
+/// Time allowed to type an answer after buzzing in, once the main clock is stopped
+pub const ANSWER_TIME: Duration = Duration::from_secs(5);
+
+/// Nine-letter word with its letters scrambled, to be unscrambled against the clock
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conundrum {
+    /// Word the anagram was made from, revealed once the round is over
+    pub word: &'static str,
+    /// Scrambled letters, in capitals
+    pub anagram: Vec<char>,
+}
+
+impl Conundrum {
+    pub fn new<R: Rng>(rng: &mut R) -> Conundrum {
+        let words: Vec<&'static str> = CONUNDRUM_LIST.lines().map(str::trim).collect();
+        let word = words
+            .choose(rng)
+            .copied()
+            .expect("Conundrum list should not be empty");
+        Conundrum::from_word(word, rng)
+    }
+
+    /// Scrambles `word`, making sure the anagram does not give the answer away by being a word
+    /// itself
+    pub fn from_word<R: Rng>(word: &'static str, rng: &mut R) -> Conundrum {
+        let mut anagram: Vec<char> = word
+            .chars()
+            .map(|value| value.to_ascii_uppercase())
+            .collect();
+        loop {
+            anagram.shuffle(rng);
+            let text: String = anagram.iter().collect();
+            if letters::check_word(&text, &anagram).is_err() {
+                return Conundrum { word, anagram };
+            }
+        }
+    }
+
+    /// `true` for the intended word, or any other listed word using all nine letters.  The
+    /// intended word is always accepted, even with a player's own word list which lacks it.
+    pub fn is_solution(&self, answer: &str) -> bool {
+        answer.trim().eq_ignore_ascii_case(self.word)
+            || letters::check_word(answer, &self.anagram) == Ok(LETTER_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Conundrum;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn from_word_scrambles_letters_of_word() {
+        // arrange
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // act
+        let conundrum = Conundrum::from_word("crocodile", &mut rng);
+
+        // assert
+        let mut letters = conundrum.anagram.clone();
+        letters.sort_unstable();
+        assert_eq!(letters, ['C', 'C', 'D', 'E', 'I', 'L', 'O', 'O', 'R']);
+        assert_ne!(conundrum.anagram.iter().collect::<String>(), "CROCODILE");
+    }
+
+    #[test]
+    fn is_solution_accepts_word_in_any_case() {
+        // arrange
+        let conundrum = Conundrum::from_word("crocodile", &mut StdRng::seed_from_u64(3));
+
+        // assert
+        assert!(conundrum.is_solution(" Crocodile "));
+        assert!(!conundrum.is_solution("crocodiles"));
+        assert!(!conundrum.is_solution("DOCILECOR"));
+        assert!(!conundrum.is_solution(""));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    fmt, io,
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::storage;
+
+const DAILY_RESULTS_FILE: &str = "daily.tsv";
+
+/// Calendar date (UTC), used to pick the daily puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |value| value.as_secs());
+        let days = i64::try_from(seconds / 86_400).expect("Days since epoch should fit in i64");
+        Date::from_days_since_epoch(days)
+    }
+
+    /// Converts days since 1970-01-01 to a Gregorian calendar date, using Howard Hinnant’s
+    /// `civil_from_days` algorithm.
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date {
+            year,
+            month: u32::try_from(month).expect("Month should be in range 1–12"),
+            day: u32::try_from(day).expect("Day should be in range 1–31"),
+        }
+    }
+
+    /// Seed shared by everyone playing the puzzle for this date
+    pub fn seed(self) -> u64 {
+        let Date { year, month, day } = self;
+        year.unsigned_abs() * 10_000 + u64::from(month) * 100 + u64::from(day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.splitn(3, '-');
+        let mut next_part = || {
+            parts
+                .next()
+                .ok_or_else(|| format!("Invalid date `{value}`, expected YYYY-MM-DD"))
+        };
+        let (year, month, day) = (next_part()?, next_part()?, next_part()?);
+        let invalid = |_| format!("Invalid date `{value}`, expected YYYY-MM-DD");
+        Ok(Date {
+            year: year.parse().map_err(invalid)?,
+            month: month.parse().map_err(invalid)?,
+            day: day.parse().map_err(invalid)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DailyOutcome {
+    /// The puzzle was revealed, but no solution submitted
+    Started,
+    Finished {
+        distance: Option<u32>,
+        expression: String,
+    },
+}
+
+impl fmt::Display for DailyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DailyOutcome::Started => write!(f, "started, but no answer was submitted"),
+            DailyOutcome::Finished {
+                distance: Some(0),
+                expression,
+            } => write!(f, "hit the target with {expression}"),
+            DailyOutcome::Finished {
+                distance: Some(distance),
+                expression,
+            } => write!(f, "{distance} away with {expression}"),
+            DailyOutcome::Finished { distance: None, .. } => write!(f, "no valid answer"),
+        }
+    }
+}
+
+fn parse_record(line: &str) -> Option<(Date, DailyOutcome)> {
+    let mut fields = line.splitn(3, '\t');
+    let date = fields.next()?.parse().ok()?;
+    let outcome = match fields.next()? {
+        "started" => DailyOutcome::Started,
+        "invalid" => DailyOutcome::Finished {
+            distance: None,
+            expression: fields.next().unwrap_or_default().to_string(),
+        },
+        distance => DailyOutcome::Finished {
+            distance: Some(distance.parse().ok()?),
+            expression: fields.next().unwrap_or_default().to_string(),
+        },
+    };
+    Some((date, outcome))
+}
+
+fn load_outcome_from(path: &Path, date: Date) -> io::Result<Option<DailyOutcome>> {
+    Ok(storage::read_lines(path)?
+        .iter()
+        .rev()
+        .filter_map(|line| parse_record(line))
+        .find(|(record_date, _)| *record_date == date)
+        .map(|(_, outcome)| outcome))
+}
+
+fn record_outcome_to(path: &Path, date: Date, outcome: &DailyOutcome) -> io::Result<()> {
+    let line = match outcome {
+        DailyOutcome::Started => format!("{date}\tstarted"),
+        DailyOutcome::Finished {
+            distance: Some(distance),
+            expression,
+        } => format!("{date}\t{distance}\t{expression}"),
+        DailyOutcome::Finished {
+            distance: None,
+            expression,
+        } => format!("{date}\tinvalid\t{expression}"),
+    };
+    storage::append_line(path, &line)
+}
+
+/// Latest saved outcome for the daily puzzle on `date`, if it has been played
+pub fn load_outcome(date: Date) -> io::Result<Option<DailyOutcome>> {
+    load_outcome_from(&storage::data_file(DAILY_RESULTS_FILE)?, date)
+}
+
+/// Saves the outcome for the daily puzzle on `date`.  Recording [`DailyOutcome::Started`] as soon
+/// as the puzzle is revealed stops players quitting and retrying for a better score.
+pub fn record_outcome(date: Date, outcome: &DailyOutcome) -> io::Result<()> {
+    record_outcome_to(&storage::data_file(DAILY_RESULTS_FILE)?, date, outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_outcome_from, record_outcome_to, DailyOutcome, Date};
+
+    #[test]
+    fn from_days_since_epoch_returns_expected_dates() {
+        // act
+        let results = (
+            Date::from_days_since_epoch(0),
+            Date::from_days_since_epoch(11_016),
+            Date::from_days_since_epoch(20_743),
+        );
+
+        // assert
+        assert_eq!(results.0.to_string(), "1970-01-01");
+        assert_eq!(results.1.to_string(), "2000-02-29");
+        assert_eq!(results.2.to_string(), "2026-10-17");
+    }
+
+    #[test]
+    fn date_parses_its_display_format() {
+        // arrange
+        let date = Date {
+            year: 2026,
+            month: 3,
+            day: 9,
+        };
+
+        // act
+        let result = date.to_string().parse::<Date>();
+
+        // assert
+        assert_eq!(result, Ok(date));
+        assert!("2026-03".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn record_outcome_keeps_latest_outcome_for_each_date() {
+        // arrange
+        let path = std::env::temp_dir().join(format!(
+            "countdown-numbers-daily-test-{}.tsv",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let date = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
+        let other_date = Date {
+            year: 2026,
+            month: 10,
+            day: 16,
+        };
+        let finished = DailyOutcome::Finished {
+            distance: Some(3),
+            expression: String::from("25 * 4 + 7"),
+        };
+
+        // act
+        let before = load_outcome_from(&path, date).unwrap();
+        record_outcome_to(&path, other_date, &DailyOutcome::Started).unwrap();
+        record_outcome_to(&path, date, &DailyOutcome::Started).unwrap();
+        let started = load_outcome_from(&path, date).unwrap();
+        record_outcome_to(&path, date, &finished).unwrap();
+        let result = load_outcome_from(&path, date).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // assert
+        assert_eq!(before, None);
+        assert_eq!(started, Some(DailyOutcome::Started));
+        assert_eq!(result, Some(finished));
+    }
+}
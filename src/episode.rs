@@ -0,0 +1,267 @@
+use std::fmt;
+
+/// Kind of round played within an episode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundKind {
+    Letters,
+    Numbers,
+    Conundrum,
+}
+
+impl RoundKind {
+    /// Round for a letter of a round sequence such as `llnllnc`
+    fn from_code(code: char) -> Option<RoundKind> {
+        match code.to_ascii_lowercase() {
+            'l' => Some(RoundKind::Letters),
+            'n' => Some(RoundKind::Numbers),
+            'c' => Some(RoundKind::Conundrum),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a round sequence written a letter per round, such as `llnllnc` for letters, letters,
+/// numbers, letters, letters, numbers, then the conundrum
+pub fn parse_rounds(value: &str) -> Result<Vec<RoundKind>, String> {
+    if value.is_empty() {
+        return Err(String::from("A game needs at least one round"));
+    }
+    value
+        .chars()
+        .map(|code| {
+            RoundKind::from_code(code).ok_or_else(|| {
+                format!(
+                    "Unknown round `{code}`, use `l` for letters, `n` for numbers or `c` for a \
+                     conundrum"
+                )
+            })
+        })
+        .collect()
+}
+
+/// Describes a round sequence, such as "3 rounds (letters, numbers, conundrum)"
+pub fn describe_rounds(rounds: &[RoundKind]) -> String {
+    let names: Vec<String> = rounds.iter().map(ToString::to_string).collect();
+    match rounds.len() {
+        1 => format!("1 round ({})", names.join(", ")),
+        count => format!("{count} rounds ({})", names.join(", ")),
+    }
+}
+
+impl fmt::Display for RoundKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundKind::Letters => write!(f, "letters"),
+            RoundKind::Numbers => write!(f, "numbers"),
+            RoundKind::Conundrum => write!(f, "conundrum"),
+        }
+    }
+}
+
+/// Preset sequence of rounds making up a full game
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EpisodeFormat {
+    /// One round of each kind
+    Short,
+
+    /// Letters, letters, numbers twice over, then the conundrum
+    #[default]
+    Standard,
+
+    /// Letters, letters, numbers three times over, then the conundrum
+    Long,
+}
+
+impl EpisodeFormat {
+    pub fn rounds(self) -> Vec<RoundKind> {
+        let (blocks, block) = match self {
+            EpisodeFormat::Short => (1, &[RoundKind::Letters, RoundKind::Numbers][..]),
+            EpisodeFormat::Standard => (2, &LETTERS_LETTERS_NUMBERS[..]),
+            EpisodeFormat::Long => (3, &LETTERS_LETTERS_NUMBERS[..]),
+        };
+        let mut rounds = block.repeat(blocks);
+        rounds.push(RoundKind::Conundrum);
+        rounds
+    }
+
+    /// Preset with exactly these `rounds`, if there is one
+    pub fn from_rounds(rounds: &[RoundKind]) -> Option<EpisodeFormat> {
+        [
+            EpisodeFormat::Short,
+            EpisodeFormat::Standard,
+            EpisodeFormat::Long,
+        ]
+        .into_iter()
+        .find(|format| format.rounds() == rounds)
+    }
+
+    /// Format after this one, for cycling through them in settings
+    pub fn next(self) -> EpisodeFormat {
+        match self {
+            EpisodeFormat::Short => EpisodeFormat::Standard,
+            EpisodeFormat::Standard => EpisodeFormat::Long,
+            EpisodeFormat::Long => EpisodeFormat::Short,
+        }
+    }
+}
+
+const LETTERS_LETTERS_NUMBERS: [RoundKind; 3] =
+    [RoundKind::Letters, RoundKind::Letters, RoundKind::Numbers];
+
+impl fmt::Display for EpisodeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", describe_rounds(&self.rounds()))
+    }
+}
+
+/// Where a game is up to, above the screens of each round
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpisodeStage {
+    /// Playing the round at this index
+    Round(usize),
+
+    /// Every round has been played, so the final scores are shown
+    Finished,
+}
+
+/// Points each player earned in a finished round
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundScore {
+    pub kind: RoundKind,
+    pub points: Vec<u32>,
+}
+
+/// Full game, stringing rounds together with a running scoreboard
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Episode {
+    pub rounds: Vec<RoundKind>,
+    pub stage: EpisodeStage,
+    /// Finished rounds, oldest first
+    pub scoreboard: Vec<RoundScore>,
+}
+
+impl Episode {
+    pub fn new(rounds: Vec<RoundKind>) -> Episode {
+        Episode {
+            rounds,
+            stage: EpisodeStage::Round(0),
+            scoreboard: Vec::new(),
+        }
+    }
+
+    /// Round being played, or `None` once the episode is finished
+    pub fn current_round(&self) -> Option<RoundKind> {
+        match self.stage {
+            EpisodeStage::Round(index) => self.rounds.get(index).copied(),
+            EpisodeStage::Finished => None,
+        }
+    }
+
+    /// Round after the current one, if there is one
+    pub fn next_round(&self) -> Option<RoundKind> {
+        match self.stage {
+            EpisodeStage::Round(index) => self.rounds.get(index + 1).copied(),
+            EpisodeStage::Finished => None,
+        }
+    }
+
+    /// Adds the current round's `points` to the scoreboard and moves on, returning the round to
+    /// play next, or `None` once the episode is finished
+    pub fn finish_round(&mut self, points: Vec<u32>) -> Option<RoundKind> {
+        let EpisodeStage::Round(index) = self.stage else {
+            return None;
+        };
+        if let Some(kind) = self.current_round() {
+            self.scoreboard.push(RoundScore { kind, points });
+        }
+        self.stage = if index + 1 < self.rounds.len() {
+            EpisodeStage::Round(index + 1)
+        } else {
+            EpisodeStage::Finished
+        };
+        self.current_round()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_rounds, Episode, EpisodeFormat, EpisodeStage, RoundKind, RoundScore};
+
+    #[test]
+    fn rounds_repeat_letters_letters_numbers_then_conundrum() {
+        // act
+        let rounds = EpisodeFormat::Standard.rounds();
+
+        // assert
+        assert_eq!(
+            rounds,
+            [
+                RoundKind::Letters,
+                RoundKind::Letters,
+                RoundKind::Numbers,
+                RoundKind::Letters,
+                RoundKind::Letters,
+                RoundKind::Numbers,
+                RoundKind::Conundrum
+            ]
+        );
+        assert_eq!(EpisodeFormat::Long.rounds().len(), 10);
+        assert_eq!(
+            EpisodeFormat::Short.to_string(),
+            "3 rounds (letters, numbers, conundrum)"
+        );
+    }
+
+    #[test]
+    fn finish_round_records_points_until_episode_is_finished() {
+        // arrange
+        let mut episode = Episode::new(EpisodeFormat::Short.rounds());
+
+        // act
+        let second = episode.finish_round(vec![7, 5]);
+        let third = episode.finish_round(vec![0, 10]);
+        let last_stage = episode.stage;
+        let after_last = episode.finish_round(vec![10, 0]);
+
+        // assert
+        assert_eq!(second, Some(RoundKind::Numbers));
+        assert_eq!(third, Some(RoundKind::Conundrum));
+        assert_eq!(last_stage, EpisodeStage::Round(2));
+        assert_eq!(after_last, None);
+        assert_eq!(episode.stage, EpisodeStage::Finished);
+        assert_eq!(
+            episode.scoreboard[1],
+            RoundScore {
+                kind: RoundKind::Numbers,
+                points: vec![0, 10]
+            }
+        );
+        assert_eq!(episode.finish_round(vec![1, 1]), None);
+        assert_eq!(episode.scoreboard.len(), 3);
+    }
+
+    #[test]
+    fn parse_rounds_reads_a_letter_per_round() {
+        // act
+        let results = (
+            parse_rounds("llnllnc"),
+            parse_rounds("NC"),
+            parse_rounds("lnx"),
+            parse_rounds(""),
+        );
+
+        // assert
+        assert_eq!(results.0, Ok(EpisodeFormat::Standard.rounds()));
+        assert_eq!(
+            results.1,
+            Ok(vec![RoundKind::Numbers, RoundKind::Conundrum])
+        );
+        assert!(results.2.is_err());
+        assert!(results.3.is_err());
+        assert_eq!(
+            EpisodeFormat::from_rounds(&EpisodeFormat::Long.rounds()),
+            Some(EpisodeFormat::Long)
+        );
+        assert_eq!(EpisodeFormat::from_rounds(&[RoundKind::Conundrum]), None);
+    }
+}
@@ -0,0 +1,682 @@
+use std::{fmt, ops::Range};
+
+/// Byte offsets of a token or sub-expression within the source text
+pub type SourceSpan = Range<usize>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+pub const OPERATORS: [Operator; 4] = [
+    Operator::Add,
+    Operator::Subtract,
+    Operator::Multiply,
+    Operator::Divide,
+];
+
+impl Operator {
+    /// Applies the operator under Countdown rules, returning `None` when the result would not be a
+    /// positive integer.
+    pub fn apply(self, left: u32, right: u32) -> Option<u32> {
+        match self {
+            Operator::Add => left.checked_add(right),
+            Operator::Subtract => (left > right).then(|| left - right),
+            Operator::Multiply => left.checked_mul(right),
+            Operator::Divide => (right != 0 && left % right == 0).then(|| left / right),
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Operator::Add | Operator::Subtract => 1,
+            Operator::Multiply | Operator::Divide => 2,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+/// How strictly intermediate results are checked when evaluating a solution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rules {
+    /// Official rules: every intermediate result must be a positive integer
+    #[default]
+    Strict,
+
+    /// Intermediate results may be negative, zero or fractional, as long as the final result is
+    /// a whole number
+    Lenient,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Number(u32),
+    Operator(Operator),
+    OpenParenthesis,
+    CloseParenthesis,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: SourceSpan,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedCharacter { character: char, span: SourceSpan },
+    NumberTooLarge { span: SourceSpan },
+    UnexpectedToken { span: SourceSpan },
+    UnclosedParenthesis { span: SourceSpan },
+    UnexpectedEnd,
+}
+
+impl ParseError {
+    /// Part of the source which could not be parsed, if the error is not just the input ending
+    /// too soon
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            ParseError::UnexpectedCharacter { span, .. }
+            | ParseError::NumberTooLarge { span }
+            | ParseError::UnexpectedToken { span }
+            | ParseError::UnclosedParenthesis { span } => Some(span.clone()),
+            ParseError::UnexpectedEnd => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedCharacter { character, .. } => {
+                write!(f, "unexpected character `{character}`")
+            }
+            ParseError::NumberTooLarge { .. } => write!(f, "number is too large"),
+            ParseError::UnexpectedToken { .. } => write!(f, "unexpected symbol"),
+            ParseError::UnclosedParenthesis { .. } => write!(f, "unclosed bracket"),
+            ParseError::UnexpectedEnd => write!(f, "expression is incomplete"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EvaluationError {
+    /// A subtraction gave zero or a negative result (strict rules)
+    NotPositive {
+        span: SourceSpan,
+    },
+    /// A division did not give a whole number (strict rules)
+    NonIntegerDivision {
+        span: SourceSpan,
+    },
+    DivisionByZero {
+        span: SourceSpan,
+    },
+    /// The final result is not a whole number (lenient rules)
+    NonIntegerResult,
+    /// The final result is negative (lenient rules)
+    NegativeResult,
+    Overflow,
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluationError::NotPositive { .. } => {
+                write!(f, "every step must give a positive number")
+            }
+            EvaluationError::NonIntegerDivision { .. } => {
+                write!(f, "division must give a whole number")
+            }
+            EvaluationError::DivisionByZero { .. } => write!(f, "cannot divide by zero"),
+            EvaluationError::NonIntegerResult => write!(f, "result must be a whole number"),
+            EvaluationError::NegativeResult => write!(f, "result must not be negative"),
+            EvaluationError::Overflow => write!(f, "result is too large"),
+        }
+    }
+}
+
+/// Splits `source` into tokens, skipping whitespace.
+pub fn tokenise(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut characters = source.char_indices().peekable();
+
+    while let Some((start, character)) = characters.next() {
+        let kind = match character {
+            '0'..='9' => {
+                let mut end = start + 1;
+                while let Some(&(index, '0'..='9')) = characters.peek() {
+                    end = index + 1;
+                    characters.next();
+                }
+                let value = source[start..end]
+                    .parse()
+                    .map_err(|_| ParseError::NumberTooLarge { span: start..end })?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(value),
+                    span: start..end,
+                });
+                continue;
+            }
+            '+' => TokenKind::Operator(Operator::Add),
+            '-' => TokenKind::Operator(Operator::Subtract),
+            '*' => TokenKind::Operator(Operator::Multiply),
+            '/' => TokenKind::Operator(Operator::Divide),
+            '(' => TokenKind::OpenParenthesis,
+            ')' => TokenKind::CloseParenthesis,
+            value if value.is_whitespace() => continue,
+            value => {
+                return Err(ParseError::UnexpectedCharacter {
+                    character: value,
+                    span: start..start + value.len_utf8(),
+                })
+            }
+        };
+        tokens.push(Token {
+            kind,
+            span: start..start + character.len_utf8(),
+        });
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expression {
+    Number {
+        value: u32,
+        span: SourceSpan,
+    },
+    Binary {
+        operator: Operator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+        span: SourceSpan,
+    },
+}
+
+impl Expression {
+    pub fn span(&self) -> SourceSpan {
+        match self {
+            Expression::Number { span, .. } | Expression::Binary { span, .. } => span.clone(),
+        }
+    }
+
+    /// Numbers used in the expression, in the order they appear.
+    pub fn numbers(&self) -> Vec<u32> {
+        let mut result = Vec::new();
+        self.collect_numbers(&mut result);
+        result
+    }
+
+    fn collect_numbers(&self, numbers: &mut Vec<u32>) {
+        match self {
+            Expression::Number { value, .. } => numbers.push(*value),
+            Expression::Binary { left, right, .. } => {
+                left.collect_numbers(numbers);
+                right.collect_numbers(numbers);
+            }
+        }
+    }
+
+    /// Final value of the expression, if it is a whole number and all intermediate results are
+    /// allowed under `rules`.
+    pub fn value(&self, rules: Rules) -> Result<u32, EvaluationError> {
+        match rules {
+            Rules::Strict => self.evaluate_strict(),
+            Rules::Lenient => {
+                let result = self.evaluate()?;
+                let Some(value) = result.as_integer() else {
+                    return Err(EvaluationError::NonIntegerResult);
+                };
+                u32::try_from(value).map_err(|_| {
+                    if value < 0 {
+                        EvaluationError::NegativeResult
+                    } else {
+                        EvaluationError::Overflow
+                    }
+                })
+            }
+        }
+    }
+
+    fn evaluate_strict(&self) -> Result<u32, EvaluationError> {
+        match self {
+            Expression::Number { value, .. } => Ok(*value),
+            Expression::Binary {
+                operator,
+                left,
+                right,
+                span,
+            } => {
+                let left = left.evaluate_strict()?;
+                let right = right.evaluate_strict()?;
+                operator.apply(left, right).ok_or(match operator {
+                    Operator::Add | Operator::Multiply => EvaluationError::Overflow,
+                    Operator::Subtract => EvaluationError::NotPositive { span: span.clone() },
+                    Operator::Divide => EvaluationError::NonIntegerDivision { span: span.clone() },
+                })
+            }
+        }
+    }
+
+    /// Evaluates the expression exactly, allowing fractional and negative intermediate values.
+    pub fn evaluate(&self) -> Result<Fraction, EvaluationError> {
+        match self {
+            Expression::Number { value, .. } => Ok(Fraction::from(*value)),
+            Expression::Binary {
+                operator,
+                left,
+                right,
+                span,
+            } => {
+                let left = left.evaluate()?;
+                let right = right.evaluate()?;
+                let result = match operator {
+                    Operator::Add => left.add(right),
+                    Operator::Subtract => left.subtract(right),
+                    Operator::Multiply => left.multiply(right),
+                    Operator::Divide => {
+                        if right.is_zero() {
+                            return Err(EvaluationError::DivisionByZero { span: span.clone() });
+                        }
+                        left.divide(right)
+                    }
+                };
+                result.ok_or(EvaluationError::Overflow)
+            }
+        }
+    }
+}
+
+/// Exact rational number, always kept in lowest terms with a positive denominator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fraction {
+    numerator: i64,
+    denominator: i64,
+}
+
+fn greatest_common_divisor(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Fraction {
+    fn new(numerator: i64, denominator: i64) -> Option<Fraction> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = greatest_common_divisor(numerator, denominator) * denominator.signum();
+        Some(Fraction {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    fn add(self, other: Fraction) -> Option<Fraction> {
+        Fraction::new(
+            self.numerator
+                .checked_mul(other.denominator)?
+                .checked_add(other.numerator.checked_mul(self.denominator)?)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    fn subtract(self, other: Fraction) -> Option<Fraction> {
+        self.add(Fraction {
+            numerator: -other.numerator,
+            denominator: other.denominator,
+        })
+    }
+
+    fn multiply(self, other: Fraction) -> Option<Fraction> {
+        Fraction::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    fn divide(self, other: Fraction) -> Option<Fraction> {
+        Fraction::new(
+            self.numerator.checked_mul(other.denominator)?,
+            self.denominator.checked_mul(other.numerator)?,
+        )
+    }
+
+    fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// Value as a whole number, if it is one
+    pub fn as_integer(self) -> Option<i64> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<u32> for Fraction {
+    fn from(value: u32) -> Self {
+        Fraction {
+            numerator: i64::from(value),
+            denominator: 1,
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Parses a binary expression using precedence climbing, only consuming operators binding
+    /// at least as tightly as `min_precedence`.
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Expression, ParseError> {
+        let mut left = self.parse_operand()?;
+
+        while let Some(Token {
+            kind: TokenKind::Operator(operator),
+            ..
+        }) = self.peek()
+        {
+            let operator = *operator;
+            if operator.precedence() < min_precedence {
+                break;
+            }
+            self.next();
+            let right = self.parse_expression(operator.precedence() + 1)?;
+            let span = left.span().start..right.span().end;
+            left = Expression::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+                span,
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expression, ParseError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Number(value),
+                span,
+            }) => Ok(Expression::Number { value, span }),
+            Some(Token {
+                kind: TokenKind::OpenParenthesis,
+                span,
+            }) => {
+                let mut expression = self.parse_expression(0)?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::CloseParenthesis,
+                        span: close_span,
+                    }) => {
+                        // the bracketed expression covers its brackets too
+                        let (Expression::Number {
+                            span: expression_span,
+                            ..
+                        }
+                        | Expression::Binary {
+                            span: expression_span,
+                            ..
+                        }) = &mut expression;
+                        *expression_span = span.start..close_span.end;
+                        Ok(expression)
+                    }
+                    Some(Token { span, .. }) => Err(ParseError::UnexpectedToken { span }),
+                    None => Err(ParseError::UnclosedParenthesis { span }),
+                }
+            }
+            Some(Token { span, .. }) => Err(ParseError::UnexpectedToken { span }),
+            None => Err(ParseError::UnexpectedEnd),
+        }
+    }
+}
+
+/// Parses an infix arithmetic expression using `+`, `-`, `*`, `/` and brackets.
+pub fn parse(source: &str) -> Result<Expression, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenise(source)?,
+        position: 0,
+    };
+    let expression = parser.parse_expression(0)?;
+    match parser.next() {
+        Some(Token { span, .. }) => Err(ParseError::UnexpectedToken { span }),
+        None => Ok(expression),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse, tokenise, EvaluationError, Expression, Fraction, Operator, ParseError, Rules,
+        TokenKind,
+    };
+
+    #[test]
+    fn operator_apply_enforces_countdown_rules() {
+        // act
+        let results = (
+            Operator::Subtract.apply(3, 5),
+            Operator::Subtract.apply(5, 5),
+            Operator::Divide.apply(10, 3),
+            Operator::Divide.apply(10, 0),
+            Operator::Divide.apply(75, 25),
+        );
+
+        // assert
+        assert_eq!(results, (None, None, None, None, Some(3)));
+    }
+
+    #[test]
+    fn tokenise_records_source_spans() {
+        // arrange
+        let input = " 25 *(3)";
+
+        // act
+        let result = tokenise(input).unwrap();
+
+        // assert
+        let kinds: Vec<TokenKind> = result.iter().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Number(25),
+                TokenKind::Operator(Operator::Multiply),
+                TokenKind::OpenParenthesis,
+                TokenKind::Number(3),
+                TokenKind::CloseParenthesis,
+            ]
+        );
+        assert_eq!(result[0].span, 1..3);
+        assert_eq!(result[4].span, 7..8);
+    }
+
+    #[test]
+    fn tokenise_rejects_unknown_characters() {
+        // act
+        let result = tokenise("2 ^ 3");
+
+        // assert
+        assert_eq!(
+            result,
+            Err(ParseError::UnexpectedCharacter {
+                character: '^',
+                span: 2..3
+            })
+        );
+    }
+
+    #[test]
+    fn parse_respects_operator_precedence_and_brackets() {
+        // act
+        let result = parse("2 + 3 * 4").unwrap();
+
+        // assert
+        assert_eq!(result.evaluate(), Ok(Fraction::from(14)));
+        assert_eq!(result.span(), 0..9);
+
+        // act
+        let result = parse("(2 + 3) * 4").unwrap();
+
+        // assert
+        assert_eq!(result.evaluate(), Ok(Fraction::from(20)));
+    }
+
+    #[test]
+    fn parse_operators_are_left_associative() {
+        // act
+        let result = parse("100 - 10 - 5").unwrap();
+
+        // assert
+        assert_eq!(result.evaluate(), Ok(Fraction::from(85)));
+
+        // act
+        let result = parse("100 / 10 / 5").unwrap();
+
+        // assert
+        assert_eq!(result.evaluate(), Ok(Fraction::from(2)));
+    }
+
+    #[test]
+    fn parse_spans_include_brackets() {
+        // act
+        let result = parse("10 / (3 - 3)").unwrap();
+
+        // assert
+        let Expression::Binary { right, span, .. } = result else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(span, 0..12);
+        assert_eq!(right.span(), 5..12);
+        assert_eq!(parse(" ((7)) ").unwrap().span(), 1..6);
+    }
+
+    #[test]
+    fn parse_reports_syntax_errors() {
+        // act
+        let results = (parse("(1 + 2"), parse("1 + 2)"), parse("1 +"), parse("1 2"));
+
+        // assert
+        assert_eq!(
+            results,
+            (
+                Err(ParseError::UnclosedParenthesis { span: 0..1 }),
+                Err(ParseError::UnexpectedToken { span: 5..6 }),
+                Err(ParseError::UnexpectedEnd),
+                Err(ParseError::UnexpectedToken { span: 2..3 }),
+            )
+        );
+    }
+
+    #[test]
+    fn numbers_lists_operands_in_order() {
+        // arrange
+        let expression = parse("(10 *2) + 3 - 2 / 1").unwrap();
+
+        // act
+        let result = expression.numbers();
+
+        // assert
+        assert_eq!(result, [10, 2, 3, 2, 1]);
+        assert!(matches!(expression, Expression::Binary { .. }));
+    }
+
+    #[test]
+    fn evaluate_keeps_exact_fractions() {
+        // act
+        let result = parse("10 / 4 * 2").unwrap().evaluate().unwrap();
+
+        // assert
+        assert_eq!(result.as_integer(), Some(5));
+
+        // act
+        let result = parse("10 / 3").unwrap().evaluate().unwrap();
+
+        // assert
+        assert_eq!(result.as_integer(), None);
+    }
+
+    #[test]
+    fn value_enforces_intermediate_results_only_under_strict_rules() {
+        // arrange
+        let negative_intermediate = parse("2 - 10 + 9").unwrap();
+        let fractional_intermediate = parse("4 / 8 * 2").unwrap();
+        let zero_intermediate = parse("(5 - 5) * 3 + 10").unwrap();
+
+        // act
+        let strict_results = (
+            negative_intermediate.value(Rules::Strict),
+            fractional_intermediate.value(Rules::Strict),
+            zero_intermediate.value(Rules::Strict),
+        );
+        let lenient_results = (
+            negative_intermediate.value(Rules::Lenient),
+            fractional_intermediate.value(Rules::Lenient),
+            zero_intermediate.value(Rules::Lenient),
+        );
+
+        // assert
+        assert_eq!(
+            strict_results,
+            (
+                Err(EvaluationError::NotPositive { span: 0..6 }),
+                Err(EvaluationError::NonIntegerDivision { span: 0..5 }),
+                Err(EvaluationError::NotPositive { span: 0..7 }),
+            )
+        );
+        assert_eq!(lenient_results, (Ok(1), Ok(1), Ok(10)));
+    }
+
+    #[test]
+    fn value_rejects_negative_final_result() {
+        // arrange
+        let expression = parse("2 - 10").unwrap();
+
+        // act
+        let result = expression.value(Rules::Lenient);
+
+        // assert
+        assert_eq!(result, Err(EvaluationError::NegativeResult));
+    }
+
+    #[test]
+    fn evaluate_reports_division_by_zero() {
+        // arrange
+        let expression = parse("10 / (3 - 3)").unwrap();
+
+        // act
+        let result = expression.evaluate();
+
+        // assert
+        assert_eq!(result, Err(EvaluationError::DivisionByZero { span: 0..12 }));
+    }
+}
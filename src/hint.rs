@@ -0,0 +1,112 @@
+use crate::solver::{Solution, Step};
+
+/// Hints available in a round, revealing progressively more of the best solution
+pub const HINT_COUNT: usize = 3;
+
+/// Operand of the final step which earlier working produced, taking the larger when both
+/// branches of the solution were worked out, so the hint is on the way to the final answer
+fn final_step_intermediate(steps: &[Step]) -> Option<u32> {
+    let (last, earlier) = steps.split_last()?;
+    [last.left, last.right]
+        .into_iter()
+        .filter(|operand| earlier.iter().any(|step| step.result == *operand))
+        .max()
+}
+
+/// Writes out hint number `level` (from zero) for a round whose best possible answer is
+/// `solution`.
+pub fn hint_text(solution: &Solution, level: usize) -> Option<String> {
+    let Solution {
+        distance, steps, ..
+    } = solution;
+    match level {
+        0 if *distance == 0 => Some(String::from("The target can be hit exactly")),
+        0 => Some(format!(
+            "The target cannot be hit exactly, but you can get within {distance}"
+        )),
+        1 => Some(match final_step_intermediate(steps) {
+            _ if steps.is_empty() => String::from("You do not need to combine any numbers"),
+            Some(value) => format!("Try making {value} along the way"),
+            None => String::from("A single operation is all you need"),
+        }),
+        2 => Some(steps.first().map_or_else(
+            || String::from("One of your numbers is already the best answer"),
+            |step| format!("Start with {step}"),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hint_text;
+    use crate::{
+        expression::Operator,
+        solver::{solve, Solution, Step},
+    };
+
+    #[test]
+    fn hint_text_reveals_progressively_more_of_solution() {
+        // arrange
+        let solution = solve(&[25, 50, 75, 100, 3, 6], 952).unwrap();
+        let steps = &solution.steps;
+
+        // act
+        let results: Vec<Option<String>> =
+            (0..4).map(|level| hint_text(&solution, level)).collect();
+
+        // assert
+        assert_eq!(results[0].as_deref(), Some("The target can be hit exactly"));
+        assert_eq!(
+            results[1],
+            Some(format!(
+                "Try making {} along the way",
+                steps[steps.len() - 2].result
+            ))
+        );
+        assert_eq!(results[2], Some(format!("Start with {}", steps[0])));
+        assert_eq!(results[3], None);
+    }
+
+    #[test]
+    fn hint_text_suggests_value_used_by_final_step() {
+        // arrange
+        let step = |left, operator, right, result| Step {
+            left,
+            operator,
+            right,
+            result,
+        };
+        // (50 + 25) * (9 - 2), with the second branch worked out last
+        let solution = Solution {
+            value: 525,
+            distance: 0,
+            steps: vec![
+                step(50, Operator::Add, 25, 75),
+                step(9, Operator::Subtract, 2, 7),
+                step(75, Operator::Multiply, 7, 525),
+            ],
+            expression: String::from("(50 + 25) * (9 - 2)"),
+        };
+        // 6 * 4 is worked out, but does not feed the final step
+        let unused_step_solution = Solution {
+            value: 300,
+            distance: 0,
+            steps: vec![
+                step(6, Operator::Multiply, 4, 24),
+                step(100, Operator::Multiply, 3, 300),
+            ],
+            expression: String::from("100 * 3"),
+        };
+
+        // act
+        let results = (hint_text(&solution, 1), hint_text(&unused_step_solution, 1));
+
+        // assert
+        assert_eq!(results.0.as_deref(), Some("Try making 75 along the way"));
+        assert_eq!(
+            results.1.as_deref(),
+            Some("A single operation is all you need")
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::{io, path::Path, time::Duration};
+
+use crate::storage;
+
+const HISTORY_FILE: &str = "history.tsv";
+
+/// Everything needed to look back at a finished round
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundRecord {
+    pub numbers: [u32; 6],
+    pub target: u32,
+    pub expression: String,
+    /// Distance from the target, or `None` if the submitted solution was not valid
+    pub distance: Option<u32>,
+    pub time_taken: Duration,
+}
+
+impl RoundRecord {
+    fn to_line(&self) -> String {
+        let RoundRecord {
+            numbers,
+            target,
+            expression,
+            distance,
+            time_taken,
+        } = self;
+        let numbers = numbers.map(|value| value.to_string()).join(" ");
+        let distance = distance.map_or_else(|| String::from("invalid"), |value| value.to_string());
+        format!(
+            "{numbers}\t{target}\t{distance}\t{}\t{expression}",
+            time_taken.as_millis()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<RoundRecord> {
+        let mut fields = line.splitn(5, '\t');
+        let mut numbers = [0; 6];
+        let mut number_values = fields.next()?.split(' ');
+        for number in &mut numbers {
+            *number = number_values.next()?.parse().ok()?;
+        }
+        let target = fields.next()?.parse().ok()?;
+        let distance = match fields.next()? {
+            "invalid" => None,
+            value => Some(value.parse().ok()?),
+        };
+        let time_taken = Duration::from_millis(fields.next()?.parse().ok()?);
+        let expression = fields.next().unwrap_or_default().to_string();
+
+        Some(RoundRecord {
+            numbers,
+            target,
+            expression,
+            distance,
+            time_taken,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statistics {
+    pub rounds_played: usize,
+    pub exact_hits: usize,
+    /// Mean distance from the target, over rounds with a valid solution
+    pub average_distance: Option<f64>,
+    /// Number of exact hits in a row, up to the latest round
+    pub current_streak: usize,
+}
+
+impl Statistics {
+    pub fn from_records(records: &[RoundRecord]) -> Statistics {
+        let distances: Vec<u32> = records.iter().filter_map(|value| value.distance).collect();
+        let average_distance = if distances.is_empty() {
+            None
+        } else {
+            Some(
+                distances.iter().copied().map(f64::from).sum::<f64>()
+                    / f64::from(u32::try_from(distances.len()).unwrap_or(u32::MAX)),
+            )
+        };
+
+        Statistics {
+            rounds_played: records.len(),
+            exact_hits: records
+                .iter()
+                .filter(|value| value.distance == Some(0))
+                .count(),
+            average_distance,
+            current_streak: records
+                .iter()
+                .rev()
+                .take_while(|value| value.distance == Some(0))
+                .count(),
+        }
+    }
+
+    /// Share of rounds where the target was hit exactly, as a percentage
+    pub fn exact_hit_rate(&self) -> Option<f64> {
+        if self.rounds_played == 0 {
+            return None;
+        }
+        let exact_hits = u32::try_from(self.exact_hits).unwrap_or(u32::MAX);
+        let rounds_played = u32::try_from(self.rounds_played).unwrap_or(u32::MAX);
+        Some(100.0 * f64::from(exact_hits) / f64::from(rounds_played))
+    }
+}
+
+fn load_records_from(path: &Path) -> io::Result<Vec<RoundRecord>> {
+    Ok(storage::read_lines(path)?
+        .iter()
+        .filter_map(|line| RoundRecord::from_line(line))
+        .collect())
+}
+
+/// Every saved round, oldest first
+pub fn load_records() -> io::Result<Vec<RoundRecord>> {
+    load_records_from(&storage::data_file(HISTORY_FILE)?)
+}
+
+pub fn append_record(record: &RoundRecord) -> io::Result<()> {
+    storage::append_line(&storage::data_file(HISTORY_FILE)?, &record.to_line())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_records_from, RoundRecord, Statistics};
+    use crate::storage;
+    use std::time::Duration;
+
+    fn record(distance: Option<u32>) -> RoundRecord {
+        RoundRecord {
+            numbers: [25, 50, 3, 6, 7, 9],
+            target: 812,
+            expression: String::from("(50 + 3) * (6 + 9) - 25 * 7 / 7"),
+            distance,
+            time_taken: Duration::from_millis(21_500),
+        }
+    }
+
+    #[test]
+    fn round_record_round_trips_through_history_file() {
+        // arrange
+        let path = std::env::temp_dir().join(format!(
+            "countdown-numbers-history-test-{}.tsv",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let records = [record(Some(0)), record(None)];
+
+        // act
+        for value in &records {
+            storage::append_line(&path, &value.to_line()).unwrap();
+        }
+        let result = load_records_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // assert
+        assert_eq!(result, records);
+    }
+
+    #[test]
+    fn statistics_summarises_rounds() {
+        // arrange
+        let records = [
+            record(Some(0)),
+            record(Some(6)),
+            record(None),
+            record(Some(0)),
+            record(Some(0)),
+        ];
+
+        // act
+        let result = Statistics::from_records(&records);
+
+        // assert
+        assert_eq!(result.rounds_played, 5);
+        assert_eq!(result.exact_hits, 3);
+        assert_eq!(result.exact_hit_rate(), Some(60.0));
+        assert_eq!(result.average_distance, Some(1.5));
+        assert_eq!(result.current_streak, 2);
+    }
+
+    #[test]
+    fn statistics_handles_no_rounds() {
+        // act
+        let result = Statistics::from_records(&[]);
+
+        // assert
+        assert_eq!(result, Statistics::default());
+        assert_eq!(result.exact_hit_rate(), None);
+    }
+}
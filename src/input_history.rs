@@ -0,0 +1,165 @@
+/// Earlier solution the player typed this round, and how close it got
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub expression: String,
+    pub distance: u32,
+}
+
+/// Solution input and cursor, saved so an edit can be undone
+type Snapshot = (String, usize);
+
+/// Undo and redo stacks for the solution input, along with earlier candidate solutions which the
+/// player can scroll back through
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputHistory {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    /// Valid solutions typed this round, oldest first
+    pub candidates: Vec<Candidate>,
+    /// Index in `candidates` being shown, while scrolling through them
+    pub position: Option<usize>,
+    /// Input from before scrolling through candidates, restored by scrolling past the newest
+    draft: Option<Snapshot>,
+}
+
+impl InputHistory {
+    /// Saves the input from before an edit, so it can be undone.  Any undone edits are
+    /// discarded.
+    pub fn record_edit(&mut self, text: &str, cursor: usize) {
+        self.undo_stack.push((text.to_string(), cursor));
+        self.redo_stack.clear();
+        self.position = None;
+        self.draft = None;
+    }
+
+    /// Adds a valid solution to the candidates, moving it to the end if it is already there
+    pub fn record_candidate(&mut self, expression: &str, distance: u32) {
+        let expression = expression.trim();
+        self.candidates
+            .retain(|candidate| candidate.expression != expression);
+        self.candidates.push(Candidate {
+            expression: expression.to_string(),
+            distance,
+        });
+    }
+
+    /// Returns `true` if the input was changed
+    pub fn undo(&mut self, text: &mut String, cursor: &mut usize) -> bool {
+        let Some((previous_text, previous_cursor)) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack
+            .push((std::mem::replace(text, previous_text), *cursor));
+        *cursor = previous_cursor;
+        self.position = None;
+        true
+    }
+
+    /// Returns `true` if the input was changed
+    pub fn redo(&mut self, text: &mut String, cursor: &mut usize) -> bool {
+        let Some((next_text, next_cursor)) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack
+            .push((std::mem::replace(text, next_text), *cursor));
+        *cursor = next_cursor;
+        self.position = None;
+        true
+    }
+
+    /// Shows the candidate before the one currently shown, starting from the newest.  Returns
+    /// `true` if the input was changed.
+    pub fn previous_candidate(&mut self, text: &mut String, cursor: &mut usize) -> bool {
+        let position = match self.position {
+            Some(0) => return false,
+            Some(value) => value - 1,
+            None if self.candidates.is_empty() => return false,
+            None => self.candidates.len() - 1,
+        };
+        self.show_candidate(position, text, cursor);
+        true
+    }
+
+    /// Shows the candidate after the one currently shown, or the input from before scrolling
+    /// once past the newest.  Returns `true` if the input was changed.
+    pub fn next_candidate(&mut self, text: &mut String, cursor: &mut usize) -> bool {
+        let Some(position) = self.position else {
+            return false;
+        };
+        if position + 1 < self.candidates.len() {
+            self.show_candidate(position + 1, text, cursor);
+        } else {
+            let draft = self.draft.take();
+            self.undo_stack.push((text.clone(), *cursor));
+            (*text, *cursor) = draft.unwrap_or_default();
+            self.position = None;
+        }
+        true
+    }
+
+    fn show_candidate(&mut self, position: usize, text: &mut String, cursor: &mut usize) {
+        let snapshot = (text.clone(), *cursor);
+        if self.position.is_none() {
+            self.draft = Some(snapshot.clone());
+        }
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+        text.clone_from(&self.candidates[position].expression);
+        *cursor = text.len();
+        self.position = Some(position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputHistory;
+
+    #[test]
+    fn undo_and_redo_restore_input() {
+        // arrange
+        let mut history = InputHistory::default();
+        let mut text = String::from("25 +");
+        let mut cursor = 4;
+        history.record_edit(&text, cursor);
+        text.push_str(" 50");
+        cursor = 7;
+
+        // act
+        let undo_result = history.undo(&mut text, &mut cursor);
+        let undone = (text.clone(), cursor);
+        let redo_result = history.redo(&mut text, &mut cursor);
+
+        // assert
+        assert!(undo_result && redo_result);
+        assert_eq!(undone, (String::from("25 +"), 4));
+        assert_eq!((text.as_str(), cursor), ("25 + 50", 7));
+        assert!(!history.redo(&mut text, &mut cursor));
+    }
+
+    #[test]
+    fn candidates_scroll_back_to_draft() {
+        // arrange
+        let mut history = InputHistory::default();
+        history.record_candidate("50 * 16", 12);
+        history.record_candidate("100 * 8", 12);
+        history.record_candidate("50 * 16 ", 12);
+        let mut text = String::from("75 +");
+        let mut cursor = 2;
+
+        // act
+        history.previous_candidate(&mut text, &mut cursor);
+        let newest = text.clone();
+        history.previous_candidate(&mut text, &mut cursor);
+        let oldest = (text.clone(), history.position);
+        let past_oldest = history.previous_candidate(&mut text, &mut cursor);
+        history.next_candidate(&mut text, &mut cursor);
+        history.next_candidate(&mut text, &mut cursor);
+
+        // assert
+        assert_eq!(newest, "50 * 16");
+        assert_eq!(oldest, (String::from("100 * 8"), Some(0)));
+        assert!(!past_oldest);
+        assert_eq!((text.as_str(), cursor), ("75 +", 2));
+        assert_eq!(history.position, None);
+    }
+}
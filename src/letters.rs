@@ -0,0 +1,322 @@
+use std::{collections::HashSet, fmt, fs, sync::OnceLock};
+
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::storage;
+
+/// Letters picked for each letters round
+pub const LETTER_COUNT: usize = 9;
+
+/// Fewest vowels and consonants allowed among the picked letters, as on the show
+pub const MIN_VOWELS: usize = 3;
+pub const MIN_CONSONANTS: usize = 4;
+
+/// Points for a word using every letter, which are doubled on the show
+const FULL_WORD_POINTS: u32 = 18;
+
+/// Copies of each vowel in the vowel pile, weighted towards the most useful letters
+const VOWEL_WEIGHTS: [(char, usize); 5] = [('A', 15), ('E', 21), ('I', 13), ('O', 13), ('U', 5)];
+
+/// Copies of each consonant in the consonant pile
+const CONSONANT_WEIGHTS: [(char, usize); 21] = [
+    ('B', 2),
+    ('C', 3),
+    ('D', 6),
+    ('F', 2),
+    ('G', 3),
+    ('H', 2),
+    ('J', 1),
+    ('K', 1),
+    ('L', 5),
+    ('M', 4),
+    ('N', 8),
+    ('P', 4),
+    ('Q', 1),
+    ('R', 9),
+    ('S', 9),
+    ('T', 9),
+    ('V', 1),
+    ('W', 1),
+    ('X', 1),
+    ('Y', 1),
+    ('Z', 1),
+];
+
+/// Lower case words, one per line, accepted in the letters round.  This hand-picked list is far
+/// from complete, so players can supply a fuller one in [`CUSTOM_WORD_LIST_FILE`].
+const WORD_LIST: &str = include_str!("../assets/words.txt");
+
+/// Word list, one word per line, which is used in place of the bundled one when it is in the data
+/// directory
+const CUSTOM_WORD_LIST_FILE: &str = "words.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LetterKind {
+    Vowel,
+    Consonant,
+}
+
+/// Shuffled vowel and consonant piles, which letters are drawn from in turn
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LetterPiles {
+    vowels: Vec<char>,
+    consonants: Vec<char>,
+}
+
+impl LetterPiles {
+    pub fn new<R: Rng>(rng: &mut R) -> LetterPiles {
+        let pile = |weights: &[(char, usize)], rng: &mut R| {
+            let mut pile: Vec<char> = weights
+                .iter()
+                .flat_map(|&(letter, count)| std::iter::repeat(letter).take(count))
+                .collect();
+            pile.shuffle(rng);
+            pile
+        };
+        LetterPiles {
+            vowels: pile(&VOWEL_WEIGHTS, rng),
+            consonants: pile(&CONSONANT_WEIGHTS, rng),
+        }
+    }
+
+    /// Takes the top letter from the pile for `kind`
+    pub fn draw(&mut self, kind: LetterKind) -> Option<char> {
+        match kind {
+            LetterKind::Vowel => self.vowels.pop(),
+            LetterKind::Consonant => self.consonants.pop(),
+        }
+    }
+
+    pub fn remaining(&self, kind: LetterKind) -> usize {
+        match kind {
+            LetterKind::Vowel => self.vowels.len(),
+            LetterKind::Consonant => self.consonants.len(),
+        }
+    }
+}
+
+pub fn is_vowel(letter: char) -> bool {
+    VOWEL_WEIGHTS
+        .iter()
+        .any(|(vowel, _)| *vowel == letter.to_ascii_uppercase())
+}
+
+/// `false` if picking another letter of `kind` would leave too few picks for the minimum number
+/// of the other kind
+pub fn can_pick(kind: LetterKind, selected_letters: &[Option<char>]) -> bool {
+    let picked: Vec<char> = selected_letters.iter().flatten().copied().collect();
+    if picked.len() >= LETTER_COUNT {
+        return false;
+    }
+    let vowel_count = picked.iter().filter(|letter| is_vowel(**letter)).count();
+    match kind {
+        LetterKind::Vowel => vowel_count < LETTER_COUNT - MIN_CONSONANTS,
+        LetterKind::Consonant => picked.len() - vowel_count < LETTER_COUNT - MIN_VOWELS,
+    }
+}
+
+/// Reason a word cannot be scored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WordError {
+    Empty,
+    LetterNotAvailable(char),
+    NotInWordList(String),
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordError::Empty => write!(f, "Enter a word"),
+            WordError::LetterNotAvailable(letter) => {
+                write!(f, "There are not enough {letter}s in your letters")
+            }
+            WordError::NotInWordList(word) => write!(f, "{word} is not in the word list"),
+        }
+    }
+}
+
+/// Words in `text` which could be played: up to nine letters, all a–z.  Anything else, such as
+/// proper nouns or words with apostrophes, is skipped.
+fn playable_words(text: &str) -> HashSet<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|word| {
+            (1..=LETTER_COUNT).contains(&word.len())
+                && word.bytes().all(|value| value.is_ascii_lowercase())
+        })
+        .collect()
+}
+
+/// Player's own word list from the data directory, if they have added one.  Tests always use the
+/// bundled list, so they do not depend on the machine they run on.
+fn custom_word_list() -> Option<String> {
+    if cfg!(test) {
+        return None;
+    }
+    fs::read_to_string(storage::data_file(CUSTOM_WORD_LIST_FILE).ok()?).ok()
+}
+
+fn word_list() -> &'static HashSet<&'static str> {
+    static WORDS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        // loaded once and kept for the rest of the session, like the bundled list
+        let text = custom_word_list().map_or(WORD_LIST, |value| Box::leak(value.into_boxed_str()));
+        playable_words(text)
+    })
+}
+
+/// `Ok` if `word` only uses the letters in `letters`, each no more times than it was picked
+fn check_letters(word: &str, letters: &[char]) -> Result<(), WordError> {
+    let mut unused: Vec<char> = letters.iter().map(char::to_ascii_uppercase).collect();
+    for letter in word.chars().map(|value| value.to_ascii_uppercase()) {
+        let Some(index) = unused.iter().position(|value| *value == letter) else {
+            return Err(WordError::LetterNotAvailable(letter));
+        };
+        unused.swap_remove(index);
+    }
+    Ok(())
+}
+
+/// Checks a word against this round's letters and the word list, returning its length
+pub fn check_word(word: &str, letters: &[char]) -> Result<usize, WordError> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(WordError::Empty);
+    }
+    check_letters(word, letters)?;
+    if !word_list().contains(word.to_ascii_lowercase().as_str()) {
+        return Err(WordError::NotInWordList(word.to_ascii_uppercase()));
+    }
+    Ok(word.len())
+}
+
+/// Every listed word which can be made from `letters`, longest first, then alphabetically
+pub fn words_from(letters: &[char]) -> Vec<&'static str> {
+    let mut words: Vec<&'static str> = word_list()
+        .iter()
+        .copied()
+        .filter(|word| word.len() <= letters.len() && check_letters(word, letters).is_ok())
+        .collect();
+    words.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    words
+}
+
+/// Longest words which can be made from `letters`, in alphabetical order
+pub fn longest_words(letters: &[char]) -> Vec<&'static str> {
+    let words = words_from(letters);
+    let longest = words.first().map_or(0, |word| word.len());
+    words
+        .into_iter()
+        .take_while(|word| word.len() == longest)
+        .collect()
+}
+
+/// Points for a valid word of `length` letters: one per letter, doubled for using all nine
+pub fn points(length: usize) -> u32 {
+    if length == LETTER_COUNT {
+        FULL_WORD_POINTS
+    } else {
+        u32::try_from(length).expect("Word length should fit in u32")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        can_pick, check_word, longest_words, playable_words, points, LetterKind, LetterPiles,
+        WordError, CONSONANT_WEIGHTS, VOWEL_WEIGHTS,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const LETTERS: [char; 9] = ['S', 'T', 'A', 'R', 'E', 'D', 'O', 'N', 'L'];
+
+    #[test]
+    fn letter_piles_hold_weighted_letters() {
+        // arrange
+        let mut piles = LetterPiles::new(&mut StdRng::seed_from_u64(7));
+        let vowel_count: usize = VOWEL_WEIGHTS.iter().map(|(_, count)| count).sum();
+        let consonant_count: usize = CONSONANT_WEIGHTS.iter().map(|(_, count)| count).sum();
+
+        // act
+        let vowels: Vec<char> = std::iter::from_fn(|| piles.draw(LetterKind::Vowel)).collect();
+
+        // assert
+        assert_eq!(vowels.len(), vowel_count);
+        assert_eq!(vowels.iter().filter(|value| **value == 'E').count(), 21);
+        assert_eq!(piles.remaining(LetterKind::Consonant), consonant_count);
+    }
+
+    #[test]
+    fn can_pick_keeps_minimum_vowels_and_consonants() {
+        // arrange
+        let five_vowels = [Some('A'), Some('E'), Some('I'), Some('O'), Some('U'), None];
+        let six_consonants = [
+            Some('B'),
+            Some('C'),
+            Some('D'),
+            Some('F'),
+            Some('G'),
+            Some('H'),
+        ];
+
+        // act
+        let results = (
+            can_pick(LetterKind::Vowel, &five_vowels),
+            can_pick(LetterKind::Consonant, &five_vowels),
+            can_pick(LetterKind::Consonant, &six_consonants),
+            can_pick(LetterKind::Vowel, &six_consonants),
+        );
+
+        // assert
+        assert_eq!(results, (false, true, false, true));
+    }
+
+    #[test]
+    fn check_word_validates_letters_and_word_list() {
+        // act
+        let results = (
+            check_word("Stone", &LETTERS),
+            check_word("  ", &LETTERS),
+            check_word("street", &LETTERS),
+            check_word("roasted", &LETTERS),
+            check_word("DNOLA", &LETTERS),
+        );
+
+        // assert
+        assert_eq!(results.0, Ok(5));
+        assert_eq!(results.1, Err(WordError::Empty));
+        assert_eq!(results.2, Err(WordError::LetterNotAvailable('E')));
+        assert_eq!(results.3, Ok(7));
+        assert_eq!(
+            results.4,
+            Err(WordError::NotInWordList(String::from("DNOLA")))
+        );
+    }
+
+    #[test]
+    fn longest_words_finds_every_word_of_the_best_length() {
+        // act
+        let result = longest_words(&['C', 'A', 'T', 'X', 'Q']);
+
+        // assert
+        assert_eq!(result, ["act", "cat", "tax"]);
+    }
+
+    #[test]
+    fn playable_words_skips_words_which_cannot_be_played() {
+        // act
+        let result = playable_words("snore\n  loaned \nLondon\ndon't\nabbreviation\n\n");
+
+        // assert
+        let mut words: Vec<&str> = result.into_iter().collect();
+        words.sort_unstable();
+        assert_eq!(words, ["loaned", "snore"]);
+    }
+
+    #[test]
+    fn points_double_for_a_nine_letter_word() {
+        // assert
+        assert_eq!(points(7), 7);
+        assert_eq!(points(9), 18);
+    }
+}
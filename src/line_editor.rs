@@ -0,0 +1,195 @@
+/// Change to a single line of text, at a cursor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    Insert(char),
+    /// Deletes the character before the cursor
+    Backspace,
+    /// Deletes the character under the cursor
+    Delete,
+    DeleteWordBackward,
+    DeleteWordForward,
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+}
+
+/// Numbers count as words, so word-wise moves jump over a whole number.  Any other character,
+/// such as an operator or bracket, is a word on its own.
+fn is_word_character(character: char) -> bool {
+    character.is_ascii_digit()
+}
+
+/// Byte index of the start of the word before `cursor`, skipping any spaces first
+fn previous_word_boundary(text: &str, cursor: usize) -> usize {
+    let mut characters = text[..cursor].char_indices().rev().peekable();
+    while characters
+        .peek()
+        .is_some_and(|(_, character)| character.is_whitespace())
+    {
+        characters.next();
+    }
+    let Some((index, character)) = characters.next() else {
+        return 0;
+    };
+    if !is_word_character(character) {
+        return index;
+    }
+    let mut boundary = index;
+    for (index, character) in characters {
+        if !is_word_character(character) {
+            break;
+        }
+        boundary = index;
+    }
+    boundary
+}
+
+/// Byte index of the end of the word after `cursor`, skipping any spaces first
+fn next_word_boundary(text: &str, cursor: usize) -> usize {
+    let mut characters = text[cursor..]
+        .char_indices()
+        .map(|(index, character)| (cursor + index, character))
+        .peekable();
+    while characters
+        .peek()
+        .is_some_and(|(_, character)| character.is_whitespace())
+    {
+        characters.next();
+    }
+    let Some((index, character)) = characters.next() else {
+        return text.len();
+    };
+    let mut boundary = index + character.len_utf8();
+    if !is_word_character(character) {
+        return boundary;
+    }
+    for (index, character) in characters {
+        if !is_word_character(character) {
+            break;
+        }
+        boundary = index + character.len_utf8();
+    }
+    boundary
+}
+
+/// Applies `edit` to `text`, with `cursor` as a byte index which is kept on a character
+/// boundary.  Returns `true` if the text changed.
+pub fn apply(text: &mut String, cursor: &mut usize, edit: Edit) -> bool {
+    *cursor = (*cursor).min(text.len());
+    let previous = text[..*cursor]
+        .chars()
+        .next_back()
+        .map_or(*cursor, |value| *cursor - value.len_utf8());
+    let next = text[*cursor..]
+        .chars()
+        .next()
+        .map_or(*cursor, |value| *cursor + value.len_utf8());
+
+    match edit {
+        Edit::Insert(character) => {
+            text.insert(*cursor, character);
+            *cursor += character.len_utf8();
+            true
+        }
+        Edit::Backspace | Edit::DeleteWordBackward => {
+            let start = if edit == Edit::Backspace {
+                previous
+            } else {
+                previous_word_boundary(text, *cursor)
+            };
+            text.replace_range(start..*cursor, "");
+            let changed = start < *cursor;
+            *cursor = start;
+            changed
+        }
+        Edit::Delete | Edit::DeleteWordForward => {
+            let end = if edit == Edit::Delete {
+                next
+            } else {
+                next_word_boundary(text, *cursor)
+            };
+            text.replace_range(*cursor..end, "");
+            end > *cursor
+        }
+        Edit::Left | Edit::Right | Edit::WordLeft | Edit::WordRight | Edit::Home | Edit::End => {
+            *cursor = match edit {
+                Edit::Left => previous,
+                Edit::Right => next,
+                Edit::WordLeft => previous_word_boundary(text, *cursor),
+                Edit::WordRight => next_word_boundary(text, *cursor),
+                Edit::Home => 0,
+                _ => text.len(),
+            };
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, Edit};
+
+    #[test]
+    fn apply_inserts_and_deletes_at_cursor() {
+        // arrange
+        let mut text = String::from("(25 + 50 * 3");
+        let mut cursor = text.len();
+
+        // act
+        apply(&mut text, &mut cursor, Edit::Home);
+        apply(&mut text, &mut cursor, Edit::Delete);
+        for _ in 0..7 {
+            apply(&mut text, &mut cursor, Edit::Right);
+        }
+        apply(&mut text, &mut cursor, Edit::Insert(')'));
+        apply(&mut text, &mut cursor, Edit::Left);
+        apply(&mut text, &mut cursor, Edit::Backspace);
+
+        // assert
+        assert_eq!(text, "25 + 5) * 3");
+        assert_eq!(cursor, 6);
+    }
+
+    #[test]
+    fn apply_edits_whole_numbers_with_word_edits() {
+        // arrange
+        let mut text = String::from("100 * 75 + 6");
+        let mut cursor = text.len();
+
+        // act
+        let changed = apply(&mut text, &mut cursor, Edit::DeleteWordBackward);
+        apply(&mut text, &mut cursor, Edit::WordLeft);
+        let operator_position = cursor;
+        apply(&mut text, &mut cursor, Edit::WordLeft);
+        apply(&mut text, &mut cursor, Edit::DeleteWordForward);
+
+        // assert
+        assert!(changed);
+        assert_eq!(operator_position, 9);
+        assert_eq!(text, "100 *  + ");
+        assert_eq!(cursor, 6);
+    }
+
+    #[test]
+    fn apply_ignores_moves_past_either_end() {
+        // arrange
+        let mut text = String::from("7");
+        let mut cursor = 0;
+
+        // act
+        let results = (
+            apply(&mut text, &mut cursor, Edit::Backspace),
+            apply(&mut text, &mut cursor, Edit::Left),
+        );
+        apply(&mut text, &mut cursor, Edit::End);
+        let delete_result = apply(&mut text, &mut cursor, Edit::Delete);
+
+        // assert
+        assert_eq!(results, (false, false));
+        assert!(!delete_result);
+        assert_eq!((text.as_str(), cursor), ("7", 1));
+    }
+}
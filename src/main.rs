@@ -1,18 +1,48 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 mod app;
+mod batch;
+mod bot;
+mod calculator;
+mod cli;
+mod conundrum;
+mod daily;
+mod episode;
+mod expression;
+mod hint;
+mod history;
+mod input_history;
+mod letters;
+mod line_editor;
+mod player;
+mod puzzle_code;
+mod scoring;
+mod solver;
+mod storage;
 mod ui;
 
 use std::{
-    io::{self},
+    io::{self, Read},
+    process::ExitCode,
     time::{Duration, Instant},
 };
 
-use app::CurrentScreen;
+use app::{CurrentScreen, Settings};
+use calculator::{Calculator, InputMode};
+use cli::{Command, PlayOptions};
+use daily::{DailyOutcome, Date};
+use episode::EpisodeFormat;
+use expression::{Operator, Rules};
+use history::Statistics;
+use input_history::InputHistory;
+use letters::LetterKind;
+use line_editor::Edit;
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     crossterm::{
-        event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        },
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
@@ -30,23 +60,127 @@ fn play_feedback_sound_effect(
 ) {
     match solution_result {
         Some(0) => sink.append(sound_effects.perfect.clone()),
-        Some(value) => {
-            if value < 11 {
-                sink.append(sound_effects.valid.clone());
-            }
-        }
-        None => {}
+        Some(value) if value < 11 => sink.append(sound_effects.valid.clone()),
+        Some(_) | None => {}
     }
 }
 
 fn update_feedback(app: &mut App) -> Option<u32> {
     let check_solution_result = app.check_solution();
+    let feedback = &mut app.player_mut().feedback;
     match check_solution_result {
-        Some(0) => app.feedback = String::from(" ✅"),
-        Some(value) => app.feedback = format!(" 📏 {value}"),
-        None => app.feedback.clear(),
+        Ok(0) => *feedback = String::from(" ✅"),
+        Ok(value) => *feedback = format!(" 📏 {value}"),
+        Err(_) => feedback.clear(),
+    }
+    check_solution_result.ok()
+}
+
+/// Saves anything which should outlive the round, once a solution is submitted.
+fn on_round_complete(app: &mut App) {
+    if let Some(record) = app.round_record() {
+        if let Err(error) = history::append_record(&record) {
+            app.notice = Some(format!("Unable to save round to history: {error}"));
+        }
+    }
+    if let Some(date) = app.daily {
+        // the first player is always a person, and the daily result is theirs
+        let player = &app.players[0];
+        let outcome = DailyOutcome::Finished {
+            distance: app.check_input(&player.value_input).ok(),
+            expression: player.value_input.trim().to_string(),
+        };
+        if let Err(error) = daily::record_outcome(date, &outcome) {
+            app.notice = Some(format!("Unable to save daily puzzle result: {error}"));
+        }
+    }
+}
+
+fn show_statistics(app: &mut App) {
+    match history::load_records() {
+        Ok(records) => app.open_statistics(Statistics::from_records(&records)),
+        Err(error) => app.notice = Some(format!("Unable to load score history: {error}")),
+    }
+}
+
+/// Starts today’s daily puzzle, unless it has already been played.
+fn start_daily_puzzle(app: &mut App) {
+    let date = Date::today();
+    match daily::load_outcome(date) {
+        Ok(Some(outcome)) => {
+            app.notice = Some(format!(
+                "You already played the daily puzzle for {date}: {outcome}"
+            ));
+        }
+        Ok(None) => {
+            *app = app.start_daily(date);
+            if let Err(error) = daily::record_outcome(date, &DailyOutcome::Started) {
+                app.notice = Some(format!("Unable to save daily puzzle result: {error}"));
+            }
+        }
+        Err(error) => {
+            app.notice = Some(format!("Unable to load daily puzzle results: {error}"));
+        }
+    }
+}
+
+const TIME_LIMIT_OPTIONS: [Duration; 4] = [
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+    Duration::from_secs(90),
+    Duration::from_secs(120),
+];
+
+fn next_time_limit(time_limit: Duration) -> Duration {
+    let index = TIME_LIMIT_OPTIONS
+        .iter()
+        .position(|&value| value == time_limit)
+        .map_or(0, |value| (value + 1) % TIME_LIMIT_OPTIONS.len());
+    TIME_LIMIT_OPTIONS[index]
+}
+
+fn handle_introduction(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Enter => app.current_screen = CurrentScreen::PickingNumbers,
+        KeyCode::Char('w') => app.current_screen = CurrentScreen::PickingLetters,
+        KeyCode::Char('c') => app.start_conundrum(),
+        KeyCode::Char('e') => app.start_episode(),
+        KeyCode::Char('f') => {
+            // a custom sequence, from `--rounds`, moves on to the first preset
+            let format = EpisodeFormat::from_rounds(&app.settings.episode_rounds)
+                .map_or(EpisodeFormat::Short, EpisodeFormat::next);
+            app.settings.episode_rounds = format.rounds();
+        }
+        KeyCode::Char('d') => start_daily_puzzle(app),
+        KeyCode::Char('s') => show_statistics(app),
+        KeyCode::Char('t') => {
+            app.settings.solvable_targets_only = !app.settings.solvable_targets_only;
+        }
+        KeyCode::Char('l') => {
+            app.settings.time_limit = next_time_limit(app.settings.time_limit);
+            app.time_remaining = app.settings.time_limit;
+        }
+        KeyCode::Char('r') => {
+            app.settings.rules = match app.settings.rules {
+                Rules::Strict => Rules::Lenient,
+                Rules::Lenient => Rules::Strict,
+            };
+        }
+        KeyCode::Char('p') => {
+            app.settings.scoring = app.settings.scoring.next();
+        }
+        KeyCode::Char('i') => {
+            app.settings.input_mode = match app.settings.input_mode {
+                InputMode::Expression => InputMode::Calculator,
+                InputMode::Calculator => InputMode::Expression,
+            };
+        }
+        KeyCode::Char('m') => {
+            app.settings.player_mode = app.settings.player_mode.next();
+            app.players = app.settings.player_mode.players();
+        }
+        _ => {}
     }
-    check_solution_result
 }
 
 fn handle_picking_numbers(
@@ -56,12 +190,10 @@ fn handle_picking_numbers(
     key_code: KeyCode,
 ) {
     match key_code {
-        KeyCode::Enter => {
-            if app.is_number_selection_complete() {
-                app.current_screen = CurrentScreen::Playing;
-                if let Some(value) = sink {
-                    value.append(sound_effects.start.clone());
-                }
+        KeyCode::Enter if app.is_number_selection_complete() => {
+            app.current_screen = CurrentScreen::Playing;
+            if let Some(value) = sink {
+                value.append(sound_effects.start.clone());
             }
         }
         KeyCode::Char(']') => {
@@ -70,52 +202,221 @@ fn handle_picking_numbers(
         KeyCode::Char('[') => {
             app.pick_random_small_number();
         }
+        KeyCode::Char(value @ '0'..='4') => {
+            if let Some(large_count) = value.to_digit(10) {
+                app.request_large_numbers(large_count as usize);
+            }
+        }
         _ => {}
     }
 }
 
-fn handle_playing(
+fn handle_picking_letters(
     app: &mut App,
     sink: Option<&Sink>,
     sound_effects: &SoundEffects,
     key_code: KeyCode,
 ) {
     match key_code {
-        KeyCode::Backspace => {
-            if let Some(value) = app.value_input.pop() {
-                if !value.is_ascii_whitespace() {
-                    let result_value = update_feedback(app);
-                    if let Some(value) = sink {
-                        play_feedback_sound_effect(result_value, value, sound_effects);
-                    }
-                }
+        KeyCode::Enter if app.is_letter_selection_complete() => {
+            app.current_screen = CurrentScreen::PlayingLetters;
+            app.time_remaining = app.settings.time_limit;
+            if let Some(value) = sink {
+                value.append(sound_effects.start.clone());
             }
         }
-        KeyCode::Char(value) => {
-            if "01234567890()+-*/ ".contains(value) {
-                app.value_input.push(value);
-                let result_value = update_feedback(app);
-                if let Some(sink_value) = sink {
-                    if !value.is_ascii_whitespace() {
-                        play_feedback_sound_effect(result_value, sink_value, sound_effects);
-                    }
+        KeyCode::Char('v') => app.pick_letter(LetterKind::Vowel),
+        KeyCode::Char('c') => app.pick_letter(LetterKind::Consonant),
+        _ => {}
+    }
+}
+
+/// Handles keys while entering a word: every letter key types, so only Esc quits.
+fn handle_playing_letters(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Char(value) => app.edit_word(Edit::Insert(value)),
+        KeyCode::Backspace => app.edit_word(Edit::Backspace),
+        KeyCode::Left => app.edit_word(Edit::Left),
+        KeyCode::Right => app.edit_word(Edit::Right),
+        KeyCode::Enter => {
+            app.submit_word();
+            if app.is_round_complete() {
+                on_round_complete(app);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Handles keys in the conundrum round: (Space) buzzes in and stops the clock, then letters type
+/// the answer.  (Enter) before buzzing in passes.
+fn handle_playing_conundrum(app: &mut App, key_code: KeyCode) {
+    let buzzed_in = app.player().buzz_time.is_some();
+    match key_code {
+        KeyCode::Char(' ') if !buzzed_in => app.buzz_in(),
+        KeyCode::Char(value) if buzzed_in => app.edit_word(Edit::Insert(value)),
+        KeyCode::Backspace => app.edit_word(Edit::Backspace),
+        KeyCode::Left => app.edit_word(Edit::Left),
+        KeyCode::Right => app.edit_word(Edit::Right),
+        KeyCode::Enter => {
+            app.submit_conundrum();
+            if app.is_round_complete() {
+                on_round_complete(app);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Maps a key to a change to the solution input.  Holding Ctrl moves or deletes a whole number
+/// at a time, and Ctrl-W deletes the number before the cursor, as in most shells.
+fn edit_for_key(key: KeyEvent) -> Option<Edit> {
+    let word_wise = key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    let edit = match key.code {
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Edit::DeleteWordBackward
+        }
+        KeyCode::Char(value) if "01234567890()+-*/ ".contains(value) => Edit::Insert(value),
+        KeyCode::Backspace if word_wise => Edit::DeleteWordBackward,
+        KeyCode::Backspace => Edit::Backspace,
+        KeyCode::Delete if word_wise => Edit::DeleteWordForward,
+        KeyCode::Delete => Edit::Delete,
+        KeyCode::Left if word_wise => Edit::WordLeft,
+        KeyCode::Left => Edit::Left,
+        KeyCode::Right if word_wise => Edit::WordRight,
+        KeyCode::Right => Edit::Right,
+        KeyCode::Home => Edit::Home,
+        KeyCode::End => Edit::End,
+        _ => return None,
+    };
+    Some(edit)
+}
+
+fn handle_playing(app: &mut App, sink: Option<&Sink>, sound_effects: &SoundEffects, key: KeyEvent) {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    let changed = match key.code {
+        KeyCode::Char('z') if control => Some(app.navigate_input(InputHistory::undo)),
+        KeyCode::Char('y') if control => Some(app.navigate_input(InputHistory::redo)),
+        KeyCode::Up => Some(app.navigate_input(InputHistory::previous_candidate)),
+        KeyCode::Down => Some(app.navigate_input(InputHistory::next_candidate)),
+        _ => None,
+    };
+    if let Some(changed) = changed {
+        if changed {
+            let result_value = update_feedback(app);
+            if let Some(value) = sink {
+                play_feedback_sound_effect(result_value, value, sound_effects);
+            }
+        }
+        return;
+    }
+
+    if let Some(edit) = edit_for_key(key) {
+        if app.edit_input(edit) {
+            let result_value = update_feedback(app);
+            if let Some(value) = sink {
+                if edit != Edit::Insert(' ') {
+                    play_feedback_sound_effect(result_value, value, sound_effects);
                 }
             }
         }
+        return;
+    }
+    match key.code {
+        KeyCode::Char('?') => app.request_hint(),
         KeyCode::Enter => {
-            app.current_screen = CurrentScreen::DisplayingResult;
+            app.submit_solution();
+            if app.is_round_complete() {
+                on_round_complete(app);
+            }
         }
         _ => {}
     }
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
+/// Handles keys while playing with step-by-step input: `1`–`6` pick a number, by position, and
+/// operators combine them.  Backspace undoes.
+fn handle_playing_calculator(
+    app: &mut App,
+    sink: Option<&Sink>,
+    sound_effects: &SoundEffects,
+    key_code: KeyCode,
+) {
+    match key_code {
+        KeyCode::Char(value @ '1'..='6') => {
+            let index = value.to_digit(10).map_or(0, |value| value as usize - 1);
+            app.update_calculator(|calculator| calculator.select_tile(index));
+        }
+        KeyCode::Char(value @ ('+' | '-' | '*' | '/')) => {
+            let operator = match value {
+                '+' => Operator::Add,
+                '-' => Operator::Subtract,
+                '*' => Operator::Multiply,
+                _ => Operator::Divide,
+            };
+            app.update_calculator(|calculator| calculator.select_operator(operator));
+            return;
+        }
+        KeyCode::Backspace => app.update_calculator(Calculator::undo),
+        KeyCode::Char('?') => {
+            app.request_hint();
+            return;
+        }
+        KeyCode::Enter => {
+            app.submit_solution();
+            if app.is_round_complete() {
+                on_round_complete(app);
+            }
+            return;
+        }
+        _ => return,
+    }
+    let result_value = update_feedback(app);
+    if let Some(value) = sink {
+        play_feedback_sound_effect(result_value, value, sound_effects);
+    }
+}
+
+/// Handles keys on the result screens, where (Enter) moves on to the next round of an episode,
+/// or otherwise plays another round of the same kind.  Returns `true` once a new round is
+/// started.
+fn handle_result(app: &mut App, key_code: KeyCode) -> bool {
+    match key_code {
+        KeyCode::Enter if app.episode.is_some() => {
+            *app = app.next_episode_round();
+            true
+        }
+        KeyCode::Enter => {
+            let next_round = app.next_round();
+            let previous_screen = std::mem::replace(app, next_round).current_screen;
+            match previous_screen {
+                CurrentScreen::LettersResult => app.current_screen = CurrentScreen::PickingLetters,
+                CurrentScreen::ConundrumResult => app.start_conundrum(),
+                _ => {}
+            }
+            true
+        }
+        KeyCode::Char('s')
+            if matches!(app.current_screen, CurrentScreen::DisplayingResult)
+                && app.episode.is_none() =>
+        {
+            show_statistics(app);
+            false
+        }
+        _ => false,
+    }
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App, mute: bool) -> io::Result<()> {
     let mut last_tick = Instant::now();
     let tick_rate = Duration::from_millis(16);
     let mut app_ui = Ui::new();
 
     // stream should not be dropped while sink is still needed
     let (_stream, stream_handle) = match OutputStream::try_default() {
+        _ if mute => (None, None),
         Ok((stream, stream_handle)) => (Some(stream), Some(stream_handle)),
         Err(error) => {
             eprintln!("Error creating getting default audio output stream: {error}");
@@ -145,27 +446,48 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                 if key.kind == event::KeyEventKind::Release {
                     continue;
                 }
-                if key.code == KeyCode::Esc || key.code == KeyCode::Char('q') {
+                let typing_word = matches!(
+                    app.current_screen,
+                    CurrentScreen::PlayingLetters | CurrentScreen::PlayingConundrum
+                );
+                if key.code == KeyCode::Esc || (key.code == KeyCode::Char('q') && !typing_word) {
                     return Ok(());
                 }
 
                 match app.current_screen {
-                    CurrentScreen::Introduction => {
-                        if key.code == KeyCode::Enter {
-                            app.current_screen = CurrentScreen::PickingNumbers;
-                        }
-                    }
+                    CurrentScreen::Introduction => handle_introduction(app, key.code),
                     CurrentScreen::PickingNumbers => {
                         handle_picking_numbers(app, sink.as_ref(), &sound_effects, key.code);
                     }
-                    CurrentScreen::Playing => {
-                        handle_playing(app, sink.as_ref(), &sound_effects, key.code);
+                    CurrentScreen::Playing => match app.settings.input_mode {
+                        InputMode::Expression => {
+                            handle_playing(app, sink.as_ref(), &sound_effects, key);
+                        }
+                        InputMode::Calculator => {
+                            handle_playing_calculator(app, sink.as_ref(), &sound_effects, key.code);
+                        }
+                    },
+                    CurrentScreen::PickingLetters => {
+                        handle_picking_letters(app, sink.as_ref(), &sound_effects, key.code);
+                    }
+                    CurrentScreen::PlayingLetters => handle_playing_letters(app, key.code),
+                    CurrentScreen::PlayingConundrum => handle_playing_conundrum(app, key.code),
+                    CurrentScreen::DisplayingResult
+                    | CurrentScreen::LettersResult
+                    | CurrentScreen::ConundrumResult => {
+                        if handle_result(app, key.code) {
+                            app_ui = Ui::new();
+                        }
                     }
-                    CurrentScreen::DisplayingResult => {
-                        // User requests replay
+                    CurrentScreen::EpisodeSummary => {
                         if key.code == KeyCode::Enter {
-                            *app = app::App::new();
-                            app.current_screen = CurrentScreen::PickingNumbers;
+                            *app = App::with_settings(app.settings.clone());
+                            app_ui = Ui::new();
+                        }
+                    }
+                    CurrentScreen::Statistics => {
+                        if key.code == KeyCode::Enter {
+                            app.close_statistics();
                             app_ui = Ui::new();
                         }
                     }
@@ -174,13 +496,33 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
         }
 
         if last_tick.elapsed() >= tick_rate {
+            if app.on_tick(last_tick.elapsed()) {
+                if app.is_round_complete() {
+                    on_round_complete(app);
+                }
+                if let Some(value) = sink.as_ref() {
+                    value.append(sound_effects.end.clone());
+                }
+            }
             app_ui.on_tick(app, sink.as_ref());
             last_tick = Instant::now();
         }
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Runs the terminal game, set up from the `play` options
+fn play(options: &PlayOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let mut settings = Settings::default();
+    if let Some(value) = options.time_limit {
+        settings.time_limit = value;
+    }
+    if let Some(value) = options.rules {
+        settings.rules = value;
+    }
+    if let Some(value) = &options.rounds {
+        settings.episode_rounds.clone_from(value);
+    }
+
     enable_raw_mode()?;
     let mut stderr = std::io::stderr();
     let _ = execute!(stderr, EnterAlternateScreen, EnableMouseCapture);
@@ -188,8 +530,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stderr);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
-    let _result = run_app(&mut terminal, &mut app);
+    let mut app = match options.seed {
+        Some(value) => App::with_seed(settings, value),
+        None => App::with_settings(settings),
+    };
+    if let Some(puzzle) = &options.puzzle {
+        app = App::from_puzzle(app.settings.clone(), puzzle);
+    }
+    if options.daily {
+        start_daily_puzzle(&mut app);
+    }
+    let _result = run_app(&mut terminal, &mut app, options.mute);
 
     disable_raw_mode()?;
     execute!(
@@ -201,3 +552,87 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Puzzles for `batch`, from the file at `path`, or stdin without one
+fn read_batch_input(path: Option<&str>) -> Result<String, String> {
+    let mut text = String::new();
+    if let Some(path) = path {
+        text = std::fs::read_to_string(path)
+            .map_err(|error| format!("Unable to read `{path}`: {error}"))?;
+    } else {
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|error| format!("Unable to read puzzles from stdin: {error}"))?;
+    }
+    Ok(text)
+}
+
+fn run(command: Command) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Command::Play(options) => play(&options)?,
+        Command::Solve {
+            numbers,
+            target,
+            all,
+        } => print!("{}", cli::solve_report(&numbers, target, all)),
+        Command::Check {
+            puzzle,
+            expression,
+            rules,
+        } => print!("{}", cli::check_report(&puzzle, &expression, rules)?),
+        Command::Batch {
+            input,
+            format,
+            rules,
+        } => {
+            let text = read_batch_input(input.as_deref())?;
+            let results = batch::solve_puzzles(&text, rules);
+            print!("{}", batch::format_results(&results, format));
+        }
+        Command::Stats => {
+            let records = history::load_records()
+                .map_err(|error| format!("Unable to load score history: {error}"))?;
+            print!("{}", cli::stats_report(&Statistics::from_records(&records)));
+        }
+        Command::Help => println!("{}", cli::USAGE),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match cli::parse_arguments(std::env::args().skip(1))
+        .map_err(Into::into)
+        .and_then(run)
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_for_key, Edit};
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn edit_for_key_uses_word_edits_with_control() {
+        // act
+        let results = (
+            edit_for_key(KeyEvent::new(KeyCode::Char('7'), KeyModifiers::NONE)),
+            edit_for_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL)),
+            edit_for_key(KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL)),
+            edit_for_key(KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE)),
+            edit_for_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)),
+        );
+
+        // assert
+        assert_eq!(results.0, Some(Edit::Insert('7')));
+        assert_eq!(results.1, Some(Edit::DeleteWordBackward));
+        assert_eq!(results.2, Some(Edit::WordLeft));
+        assert_eq!(results.3, Some(Edit::Delete));
+        assert_eq!(results.4, None);
+    }
+}
@@ -0,0 +1,109 @@
+use std::{fmt, time::Duration};
+
+use crate::{
+    bot::BotStrength,
+    calculator::Calculator,
+    input_history::{Candidate, InputHistory},
+};
+
+/// Who is taking part in each round
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerMode {
+    #[default]
+    Single,
+
+    /// Two players sharing the terminal, entering solutions in turn
+    HotSeat,
+
+    /// One player against a computer opponent
+    Computer(BotStrength),
+}
+
+impl PlayerMode {
+    /// Fresh players for a session in this mode
+    pub fn players(self) -> Vec<Player> {
+        match self {
+            PlayerMode::Single => vec![Player::new("Player 1")],
+            PlayerMode::HotSeat => vec![Player::new("Player 1"), Player::new("Player 2")],
+            PlayerMode::Computer(strength) => vec![Player::new("You"), Player::bot(strength)],
+        }
+    }
+
+    /// Mode after this one, for cycling through them in settings
+    pub fn next(self) -> PlayerMode {
+        match self {
+            PlayerMode::Single => PlayerMode::HotSeat,
+            PlayerMode::HotSeat => PlayerMode::Computer(BotStrength::Beginner),
+            PlayerMode::Computer(BotStrength::Beginner) => {
+                PlayerMode::Computer(BotStrength::Intermediate)
+            }
+            PlayerMode::Computer(BotStrength::Intermediate) => {
+                PlayerMode::Computer(BotStrength::Expert)
+            }
+            PlayerMode::Computer(BotStrength::Expert) => PlayerMode::Single,
+        }
+    }
+}
+
+impl fmt::Display for PlayerMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerMode::Single => write!(f, "one player"),
+            PlayerMode::HotSeat => write!(f, "two players, taking turns at this terminal"),
+            PlayerMode::Computer(strength) => write!(f, "against the computer ({strength})"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub value_input: String,
+    /// Byte index in `value_input` where typing is inserted
+    pub cursor: usize,
+    /// Undo stack and earlier candidates for `value_input`
+    pub input_history: InputHistory,
+    /// Closest valid solution entered this round
+    pub best_attempt: Option<Candidate>,
+    /// Input at the time of submitting, when the best attempt was scored instead
+    pub final_input: Option<String>,
+    pub feedback: String,
+    /// Points earned this round, once every player has submitted
+    pub round_points: Option<u32>,
+    /// Running total of points, carried across rounds in this session
+    pub score: u32,
+    /// Strength of a computer player, or `None` for a person
+    pub bot: Option<BotStrength>,
+    /// Hints revealed this round, each costing points
+    pub hints: Vec<String>,
+    /// Step-by-step working, when entering solutions with the calculator
+    pub calculator: Option<Calculator>,
+    /// Time into the conundrum round when the player buzzed in, if they did
+    pub buzz_time: Option<Duration>,
+}
+
+impl Player {
+    pub fn new(name: &str) -> Player {
+        Player {
+            name: name.to_string(),
+            ..Player::default()
+        }
+    }
+
+    /// Computer opponent, which enters its solution as soon as the other player submits
+    pub fn bot(strength: BotStrength) -> Player {
+        Player {
+            bot: Some(strength),
+            ..Player::new("Computer")
+        }
+    }
+
+    /// Same player, with a clear input ready for another round
+    pub fn next_round(&self) -> Player {
+        Player {
+            score: self.score,
+            bot: self.bot,
+            ..Player::new(&self.name)
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use crate::app::{MAX_TARGET, MIN_TARGET};
+
+/// Numbers which can be picked, in the order used for encoding
+const NUMBER_SYMBOLS: [u32; 14] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 25, 50, 75, 100];
+
+/// Crockford base32 alphabet, which leaves out I, L, O and U to avoid misreading codes
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Seven characters carry the numbers and target, with one more for a checksum
+const CODE_LENGTH: usize = 8;
+
+const NUMBER_BITS: u32 = 4;
+const TARGET_BITS: u32 = 10;
+const SYMBOL_BITS: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub numbers: [u32; 6],
+    pub target: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PuzzleCodeError {
+    InvalidLength(usize),
+    InvalidCharacter(char),
+    ChecksumMismatch,
+    NumberNotAvailable(u32),
+    NumberUsedTooOften(u32),
+    TargetOutOfRange(u32),
+}
+
+impl fmt::Display for PuzzleCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleCodeError::InvalidLength(length) => write!(
+                f,
+                "Puzzle code should have {CODE_LENGTH} characters, but has {length}"
+            ),
+            PuzzleCodeError::InvalidCharacter(character) => {
+                write!(f, "Puzzle code cannot contain `{character}`")
+            }
+            PuzzleCodeError::ChecksumMismatch => {
+                write!(f, "Puzzle code is not valid, check for typos")
+            }
+            PuzzleCodeError::NumberNotAvailable(value) => {
+                write!(f, "{value} is not one of the numbers which can be picked")
+            }
+            PuzzleCodeError::NumberUsedTooOften(value) => {
+                write!(f, "{value} appears more times than it can be picked")
+            }
+            PuzzleCodeError::TargetOutOfRange(value) => write!(
+                f,
+                "Target {value} is outside the range {MIN_TARGET}–{}",
+                MAX_TARGET - 1
+            ),
+        }
+    }
+}
+
+impl Puzzle {
+    /// Checks the numbers could have been picked from the usual pool of 25, 50, 75 and 100, plus two
+    /// each of 1–10, and that the target is in range.
+    pub fn new(numbers: [u32; 6], target: u32) -> Result<Puzzle, PuzzleCodeError> {
+        for number in numbers {
+            let allowed = match number {
+                1..=10 => 2,
+                25 | 50 | 75 | 100 => 1,
+                _ => return Err(PuzzleCodeError::NumberNotAvailable(number)),
+            };
+            if numbers.iter().filter(|&&value| value == number).count() > allowed {
+                return Err(PuzzleCodeError::NumberUsedTooOften(number));
+            }
+        }
+        if !(MIN_TARGET..MAX_TARGET).contains(&target) {
+            return Err(PuzzleCodeError::TargetOutOfRange(target));
+        }
+        Ok(Puzzle { numbers, target })
+    }
+
+    /// Compact code for sharing the puzzle, such as `AP9B8P81`
+    pub fn code(&self) -> String {
+        let packed = self.numbers.iter().fold(0_u64, |accum, number| {
+            let index = NUMBER_SYMBOLS
+                .iter()
+                .position(|value| value == number)
+                .expect("Puzzle numbers should be validated on creation");
+            (accum << NUMBER_BITS) | index as u64
+        });
+        let packed = (packed << TARGET_BITS) | u64::from(self.target - MIN_TARGET);
+
+        let mut symbols: Vec<usize> = (0..CODE_LENGTH - 1)
+            .rev()
+            .map(|position| {
+                usize::try_from((packed >> (position * SYMBOL_BITS as usize)) & 0x1f)
+                    .expect("Five bit value should fit in usize")
+            })
+            .collect();
+        symbols.push(checksum(&symbols));
+
+        symbols
+            .into_iter()
+            .map(|symbol| char::from(ALPHABET[symbol]))
+            .collect()
+    }
+
+    /// Reads a puzzle from its code.  Case and any `-` or space separators are ignored.
+    pub fn from_code(code: &str) -> Result<Puzzle, PuzzleCodeError> {
+        let symbols = code
+            .chars()
+            .filter(|character| *character != '-' && !character.is_whitespace())
+            .map(|character| {
+                ALPHABET
+                    .iter()
+                    .position(|&value| char::from(value) == character.to_ascii_uppercase())
+                    .ok_or(PuzzleCodeError::InvalidCharacter(character))
+            })
+            .collect::<Result<Vec<usize>, PuzzleCodeError>>()?;
+
+        if symbols.len() != CODE_LENGTH {
+            return Err(PuzzleCodeError::InvalidLength(symbols.len()));
+        }
+        let (data, check) = symbols.split_at(CODE_LENGTH - 1);
+        if checksum(data) != check[0] {
+            return Err(PuzzleCodeError::ChecksumMismatch);
+        }
+
+        let packed = data.iter().fold(0_u64, |accum, &symbol| {
+            (accum << SYMBOL_BITS) | symbol as u64
+        });
+        let target = u32::try_from(packed & ((1 << TARGET_BITS) - 1))
+            .expect("Ten bit value should fit in u32")
+            + MIN_TARGET;
+        let mut numbers = [0; 6];
+        for (position, number) in numbers.iter_mut().rev().enumerate() {
+            let shift = TARGET_BITS + NUMBER_BITS * u32::try_from(position).expect("Small index");
+            let index = usize::try_from((packed >> shift) & 0xf).expect("Small index");
+            *number = *NUMBER_SYMBOLS
+                .get(index)
+                .ok_or(PuzzleCodeError::ChecksumMismatch)?;
+        }
+
+        Puzzle::new(numbers, target)
+    }
+}
+
+fn checksum(symbols: &[usize]) -> usize {
+    symbols
+        .iter()
+        .enumerate()
+        .map(|(position, symbol)| (position + 1) * symbol)
+        .sum::<usize>()
+        % ALPHABET.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, PuzzleCodeError};
+
+    #[test]
+    fn code_round_trips_puzzle() {
+        // arrange
+        let puzzle = Puzzle::new([100, 75, 1, 1, 10, 9], 999).unwrap();
+
+        // act
+        let code = puzzle.code();
+        let result = Puzzle::from_code(&code);
+
+        // assert
+        assert_eq!(code.len(), 8);
+        assert_eq!(result, Ok(puzzle));
+        assert_eq!(
+            Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap().code(),
+            "AP9B8P81"
+        );
+    }
+
+    #[test]
+    fn from_code_ignores_case_and_separators() {
+        // arrange
+        let puzzle = Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap();
+        let code = puzzle.code().to_lowercase();
+        let code = format!("{}-{}", &code[..4], &code[4..]);
+
+        // act
+        let result = Puzzle::from_code(&code);
+
+        // assert
+        assert_eq!(result, Ok(puzzle));
+    }
+
+    #[test]
+    fn from_code_rejects_mistyped_code() {
+        // arrange
+        let code = Puzzle::new([25, 50, 3, 6, 7, 9], 812).unwrap().code();
+        let mut mistyped: Vec<char> = code.chars().collect();
+        mistyped[2] = if mistyped[2] == '0' { '1' } else { '0' };
+        let mistyped: String = mistyped.into_iter().collect();
+
+        // act
+        let results = (
+            Puzzle::from_code(&mistyped),
+            Puzzle::from_code(&code[..7]),
+            Puzzle::from_code("0000000I"),
+        );
+
+        // assert
+        assert_eq!(results.0, Err(PuzzleCodeError::ChecksumMismatch));
+        assert_eq!(results.1, Err(PuzzleCodeError::InvalidLength(7)));
+        assert_eq!(results.2, Err(PuzzleCodeError::InvalidCharacter('I')));
+    }
+
+    #[test]
+    fn new_rejects_numbers_outside_legal_pool() {
+        // act
+        let results = (
+            Puzzle::new([25, 25, 3, 6, 7, 9], 812),
+            Puzzle::new([1, 1, 1, 6, 7, 9], 812),
+            Puzzle::new([20, 50, 3, 6, 7, 9], 812),
+            Puzzle::new([25, 50, 3, 6, 7, 9], 1_000),
+        );
+
+        // assert
+        assert_eq!(results.0, Err(PuzzleCodeError::NumberUsedTooOften(25)));
+        assert_eq!(results.1, Err(PuzzleCodeError::NumberUsedTooOften(1)));
+        assert_eq!(results.2, Err(PuzzleCodeError::NumberNotAvailable(20)));
+        assert_eq!(results.3, Err(PuzzleCodeError::TargetOutOfRange(1_000)));
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+
+/// How many points a solution earns, from its distance to the target
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scoring {
+    /// Rules from the show: 10 points for an exact hit, 7 for 1–5 away and 5 for 6–10 away
+    #[default]
+    Countdown,
+
+    /// 10 points for an exact hit, and nothing otherwise
+    ExactOnly,
+
+    /// 10 points for an exact hit, with one point fewer for each step away
+    Sliding,
+}
+
+pub const MAX_POINTS: u32 = 10;
+
+/// Points taken off a round's score for each hint used
+pub const HINT_PENALTY: u32 = 2;
+
+impl Scoring {
+    /// Points for a solution `distance` away from the target, or no points for an invalid
+    /// solution
+    pub fn points(self, distance: Option<u32>) -> u32 {
+        let Some(distance) = distance else {
+            return 0;
+        };
+        match (self, distance) {
+            (_, 0) => MAX_POINTS,
+            (Scoring::Countdown, 1..=5) => 7,
+            (Scoring::Countdown, 6..=10) => 5,
+            (Scoring::Sliding, _) => MAX_POINTS.saturating_sub(distance),
+            (Scoring::Countdown | Scoring::ExactOnly, _) => 0,
+        }
+    }
+
+    /// Scoring option after this one, for cycling through them in settings
+    pub fn next(self) -> Scoring {
+        match self {
+            Scoring::Countdown => Scoring::ExactOnly,
+            Scoring::ExactOnly => Scoring::Sliding,
+            Scoring::Sliding => Scoring::Countdown,
+        }
+    }
+}
+
+impl fmt::Display for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scoring::Countdown => write!(f, "countdown (10 exact, 7 within 5, 5 within 10)"),
+            Scoring::ExactOnly => write!(f, "exact only (10 for an exact hit)"),
+            Scoring::Sliding => write!(f, "sliding (10, less 1 for each step away)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scoring;
+
+    #[test]
+    fn countdown_scoring_awards_expected_points() {
+        // act
+        let results = [None, Some(0), Some(1), Some(5), Some(6), Some(10), Some(11)]
+            .map(|distance| Scoring::Countdown.points(distance));
+
+        // assert
+        assert_eq!(results, [0, 10, 7, 7, 5, 5, 0]);
+    }
+
+    #[test]
+    fn alternative_scoring_awards_expected_points() {
+        // act
+        let exact_only = [Some(0), Some(1)].map(|distance| Scoring::ExactOnly.points(distance));
+        let sliding = [None, Some(0), Some(3), Some(10), Some(25)]
+            .map(|distance| Scoring::Sliding.points(distance));
+
+        // assert
+        assert_eq!(exact_only, [10, 0]);
+        assert_eq!(sliding, [0, 10, 7, 0, 0]);
+    }
+}
@@ -0,0 +1,543 @@
+use std::{collections::HashSet, fmt};
+
+use crate::expression::{Operator, OPERATORS};
+
+/// A single arithmetic operation combining two numbers, as written out on the whiteboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub left: u32,
+    pub operator: Operator,
+    pub right: u32,
+    pub result: u32,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Step {
+            left,
+            operator,
+            right,
+            result,
+        } = self;
+        write!(f, "{left} {operator} {right} = {result}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub value: u32,
+    pub distance: u32,
+    pub steps: Vec<Step>,
+    pub expression: String,
+}
+
+struct Search {
+    target: u32,
+    best_value: u32,
+    best_distance: u32,
+    best_steps: Vec<Step>,
+    steps: Vec<Step>,
+    max_steps: usize,
+}
+
+impl Search {
+    fn consider(&mut self, value: u32) {
+        let distance = value.abs_diff(self.target);
+        if distance < self.best_distance {
+            self.best_value = value;
+            self.best_distance = distance;
+            self.best_steps.clone_from(&self.steps);
+        }
+    }
+
+    fn search(&mut self, tiles: &[u32]) {
+        for (left_index, &left) in tiles.iter().enumerate() {
+            for (right_index, &right) in tiles.iter().enumerate() {
+                // consider each pair once, with the larger number on the left
+                if left_index == right_index
+                    || left < right
+                    || (left == right && left_index > right_index)
+                {
+                    continue;
+                }
+
+                for operator in OPERATORS {
+                    if self.best_distance == 0 {
+                        return;
+                    }
+                    let Some(result) = operator.apply(left, right) else {
+                        continue;
+                    };
+
+                    // skip operations which only reproduce one of their inputs
+                    if result == right
+                        || (right == 1 && matches!(operator, Operator::Multiply | Operator::Divide))
+                    {
+                        continue;
+                    }
+
+                    self.steps.push(Step {
+                        left,
+                        operator,
+                        right,
+                        result,
+                    });
+                    self.consider(result);
+
+                    if tiles.len() > 2 && self.steps.len() < self.max_steps {
+                        let mut remaining_tiles: Vec<u32> = tiles
+                            .iter()
+                            .enumerate()
+                            .filter(|&(index, _)| index != left_index && index != right_index)
+                            .map(|(_, &value)| value)
+                            .collect();
+                        remaining_tiles.push(result);
+                        self.search(&remaining_tiles);
+                    }
+                    self.steps.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Replays `steps` against `numbers` to write out the working as a single infix expression.
+fn expression_from_steps(numbers: &[u32], steps: &[Step]) -> Option<String> {
+    let mut tiles: Vec<(u32, String)> = numbers
+        .iter()
+        .map(|value| (*value, value.to_string()))
+        .collect();
+
+    for Step {
+        left,
+        operator,
+        right,
+        result,
+    } in steps
+    {
+        let left_index = tiles.iter().position(|(value, _)| value == left)?;
+        let (_, left_expression) = tiles.swap_remove(left_index);
+        let right_index = tiles.iter().position(|(value, _)| value == right)?;
+        let (_, right_expression) = tiles.swap_remove(right_index);
+        tiles.push((
+            *result,
+            format!("({left_expression} {operator} {right_expression})"),
+        ));
+    }
+
+    let (_, expression) = tiles.pop()?;
+    if steps.is_empty() {
+        return Some(expression);
+    }
+    Some(expression[1..expression.len() - 1].to_string())
+}
+
+/// Working with the order of operands and the grouping of repeated additions and subtractions,
+/// or multiplications and divisions, taken out, so `(3 + 2) + 1`, `3 + (2 + 1)` and `(1 + 3) + 2`
+/// share one form, as do `9 - (7 - 3)` and `(9 + 3) - 7`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum NormalForm {
+    Number(u32),
+    Sum {
+        added: Vec<NormalForm>,
+        subtracted: Vec<NormalForm>,
+    },
+    Product {
+        multiplied: Vec<NormalForm>,
+        divided: Vec<NormalForm>,
+    },
+}
+
+impl NormalForm {
+    /// Terms added and subtracted, treating anything other than a sum as a single added term
+    fn into_terms(self) -> (Vec<NormalForm>, Vec<NormalForm>) {
+        match self {
+            NormalForm::Sum { added, subtracted } => (added, subtracted),
+            other => (vec![other], Vec::new()),
+        }
+    }
+
+    /// Factors multiplied and divided, treating anything other than a product as a single
+    /// multiplied factor
+    fn into_factors(self) -> (Vec<NormalForm>, Vec<NormalForm>) {
+        match self {
+            NormalForm::Product {
+                multiplied,
+                divided,
+            } => (multiplied, divided),
+            other => (vec![other], Vec::new()),
+        }
+    }
+
+    fn combine(left: NormalForm, operator: Operator, right: NormalForm) -> NormalForm {
+        let (mut positive, mut negative, right_positive, right_negative) = match operator {
+            Operator::Add | Operator::Subtract => {
+                let (left_positive, left_negative) = left.into_terms();
+                let (right_positive, right_negative) = right.into_terms();
+                (left_positive, left_negative, right_positive, right_negative)
+            }
+            Operator::Multiply | Operator::Divide => {
+                let (left_positive, left_negative) = left.into_factors();
+                let (right_positive, right_negative) = right.into_factors();
+                (left_positive, left_negative, right_positive, right_negative)
+            }
+        };
+        if matches!(operator, Operator::Add | Operator::Multiply) {
+            positive.extend(right_positive);
+            negative.extend(right_negative);
+        } else {
+            positive.extend(right_negative);
+            negative.extend(right_positive);
+        }
+        positive.sort_unstable();
+        negative.sort_unstable();
+
+        match operator {
+            Operator::Add | Operator::Subtract => NormalForm::Sum {
+                added: positive,
+                subtracted: negative,
+            },
+            Operator::Multiply | Operator::Divide => NormalForm::Product {
+                multiplied: positive,
+                divided: negative,
+            },
+        }
+    }
+}
+
+/// Replays `steps` against `numbers`, as [`expression_from_steps`] does, to find the normal form
+/// of the working.
+fn normal_form_from_steps(numbers: &[u32], steps: &[Step]) -> Option<NormalForm> {
+    let mut tiles: Vec<(u32, NormalForm)> = numbers
+        .iter()
+        .map(|value| (*value, NormalForm::Number(*value)))
+        .collect();
+
+    for Step {
+        left,
+        operator,
+        right,
+        result,
+    } in steps
+    {
+        let left_index = tiles.iter().position(|(value, _)| value == left)?;
+        let (_, left_form) = tiles.swap_remove(left_index);
+        let right_index = tiles.iter().position(|(value, _)| value == right)?;
+        let (_, right_form) = tiles.swap_remove(right_index);
+        tiles.push((
+            *result,
+            NormalForm::combine(left_form, *operator, right_form),
+        ));
+    }
+
+    tiles.pop().map(|(_, form)| form)
+}
+
+/// Searches every combination of `numbers` allowed under Countdown rules (each number used at
+/// most once, with only positive integer intermediate results) for the value closest to
+/// `target`.  Returns `None` if there are no numbers to work with.
+pub fn solve(numbers: &[u32], target: u32) -> Option<Solution> {
+    solve_in_steps(numbers, target, usize::MAX)
+}
+
+/// Like [`solve`], but only considers working with at most `max_steps` operations.
+pub fn solve_in_steps(numbers: &[u32], target: u32, max_steps: usize) -> Option<Solution> {
+    let mut search = Search {
+        target,
+        best_value: 0,
+        best_distance: u32::MAX,
+        best_steps: Vec::new(),
+        steps: Vec::new(),
+        max_steps,
+    };
+
+    for &value in numbers {
+        search.consider(value);
+    }
+    if search.best_distance == u32::MAX {
+        return None;
+    }
+    search.search(numbers);
+
+    let Search {
+        best_value,
+        best_distance,
+        best_steps,
+        ..
+    } = search;
+
+    let expression = if best_steps.is_empty() {
+        best_value.to_string()
+    } else {
+        expression_from_steps(numbers, &best_steps)
+            .expect("Solution steps should only use available numbers")
+    };
+
+    Some(Solution {
+        value: best_value,
+        distance: best_distance,
+        steps: best_steps,
+        expression,
+    })
+}
+
+/// Adds the working for every exact solution reachable from `tiles` to `found`, skipping
+/// operations which only reproduce one of their inputs, as [`solve`] does
+fn collect_exact_solutions(
+    numbers: &[u32],
+    tiles: &[u32],
+    target: u32,
+    steps: &mut Vec<Step>,
+    found: &mut Vec<String>,
+    forms: &mut HashSet<NormalForm>,
+) {
+    for (left_index, &left) in tiles.iter().enumerate() {
+        for (right_index, &right) in tiles.iter().enumerate() {
+            if left_index == right_index
+                || left < right
+                || (left == right && left_index > right_index)
+            {
+                continue;
+            }
+
+            for operator in OPERATORS {
+                let Some(result) = operator.apply(left, right) else {
+                    continue;
+                };
+                if result == right
+                    || (right == 1 && matches!(operator, Operator::Multiply | Operator::Divide))
+                {
+                    continue;
+                }
+
+                steps.push(Step {
+                    left,
+                    operator,
+                    right,
+                    result,
+                });
+                if result == target {
+                    let form = normal_form_from_steps(numbers, steps);
+                    if let (Some(form), Some(expression)) =
+                        (form, expression_from_steps(numbers, steps))
+                    {
+                        if forms.insert(form) {
+                            found.push(expression);
+                        }
+                    }
+                } else if tiles.len() > 2 {
+                    let mut remaining_tiles: Vec<u32> = tiles
+                        .iter()
+                        .enumerate()
+                        .filter(|&(index, _)| index != left_index && index != right_index)
+                        .map(|(_, &value)| value)
+                        .collect();
+                    remaining_tiles.push(result);
+                    collect_exact_solutions(numbers, &remaining_tiles, target, steps, found, forms);
+                }
+                steps.pop();
+            }
+        }
+    }
+}
+
+/// Every distinct exact solution, in the order they are found.  Solutions which only differ in
+/// the order of operands, or in how repeated additions and subtractions, or multiplications and
+/// divisions, are grouped, count as one, so `(3 + 2) + 1` and `3 + (2 + 1)` give a single
+/// solution.  Unlike [`solve`], the search carries on past the first exact solution, so takes
+/// longer.
+pub fn exact_solutions(numbers: &[u32], target: u32) -> Vec<String> {
+    let mut found: Vec<String> = numbers
+        .iter()
+        .filter(|&&value| value == target)
+        .take(1)
+        .map(ToString::to_string)
+        .collect();
+    collect_exact_solutions(
+        numbers,
+        numbers,
+        target,
+        &mut Vec::new(),
+        &mut found,
+        &mut HashSet::new(),
+    );
+    found
+}
+
+fn collect_reachable_values(
+    tiles: &mut [u32],
+    visited: &mut HashSet<Vec<u32>>,
+    reachable: &mut HashSet<u32>,
+) {
+    tiles.sort_unstable();
+    if !visited.insert(tiles.to_vec()) {
+        return;
+    }
+    reachable.extend(tiles.iter().copied());
+
+    for left_index in 0..tiles.len() {
+        for right_index in 0..left_index {
+            // tiles are sorted, so the larger number is on the left
+            let (left, right) = (tiles[left_index], tiles[right_index]);
+            for operator in OPERATORS {
+                if let Some(result) = operator.apply(left, right) {
+                    let mut remaining_tiles: Vec<u32> = tiles
+                        .iter()
+                        .enumerate()
+                        .filter(|&(index, _)| index != left_index && index != right_index)
+                        .map(|(_, &value)| value)
+                        .collect();
+                    remaining_tiles.push(result);
+                    collect_reachable_values(&mut remaining_tiles, visited, reachable);
+                }
+            }
+        }
+    }
+}
+
+/// Every value which can be made exactly from `numbers` under Countdown rules.
+pub fn reachable_values(numbers: &[u32]) -> HashSet<u32> {
+    let mut reachable = HashSet::new();
+    collect_reachable_values(&mut numbers.to_vec(), &mut HashSet::new(), &mut reachable);
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        exact_solutions, expression_from_steps, reachable_values, solve, solve_in_steps, Operator,
+        Step,
+    };
+
+    #[test]
+    fn expression_from_steps_writes_out_working() {
+        // arrange
+        let numbers = [25, 50, 3, 6, 7, 9];
+        let steps = [
+            Step {
+                left: 50,
+                operator: Operator::Add,
+                right: 25,
+                result: 75,
+            },
+            Step {
+                left: 75,
+                operator: Operator::Multiply,
+                right: 3,
+                result: 225,
+            },
+        ];
+
+        // act
+        let result = expression_from_steps(&numbers, &steps);
+
+        // assert
+        assert_eq!(result, Some(String::from("(50 + 25) * 3")));
+    }
+
+    #[test]
+    fn solve_finds_exact_solution() {
+        // arrange
+        let numbers = [25, 50, 75, 100, 3, 6];
+
+        // act
+        let result = solve(&numbers, 952).unwrap();
+
+        // assert
+        assert_eq!(result.value, 952);
+        assert_eq!(result.distance, 0);
+        assert_eq!(result.steps.last().unwrap().result, 952);
+    }
+
+    #[test]
+    fn solve_in_steps_limits_working() {
+        // arrange
+        let numbers = [25, 50, 75, 100, 3, 6];
+
+        // act
+        let result = solve_in_steps(&numbers, 952, 2).unwrap();
+
+        // assert
+        assert!(result.steps.len() <= 2);
+        assert!(result.distance > 0);
+        assert_eq!(solve_in_steps(&numbers, 900, 2).unwrap().distance, 0);
+    }
+
+    #[test]
+    fn solve_returns_closest_value_when_target_is_unreachable() {
+        // arrange
+        let numbers = [1, 1, 1, 1, 1, 1];
+
+        // act
+        let result = solve(&numbers, 999).unwrap();
+
+        // assert
+        assert_eq!(result.value, 9);
+        assert_eq!(result.distance, 990);
+    }
+
+    #[test]
+    fn solve_accepts_a_single_selected_number() {
+        // arrange
+        let numbers = [100, 1, 2, 3, 4, 5];
+
+        // act
+        let result = solve(&numbers, 100).unwrap();
+
+        // assert
+        assert_eq!(result.expression, "100");
+        assert!(result.steps.is_empty());
+    }
+
+    #[test]
+    fn solve_returns_none_without_numbers() {
+        // act
+        let result = solve(&[], 100);
+
+        // assert
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn exact_solutions_lists_each_way_to_hit_target_once() {
+        // act
+        let result = exact_solutions(&[1, 2, 3], 6);
+
+        // assert
+        assert_eq!(result, [String::from("3 + (2 + 1)"), String::from("3 * 2")]);
+        assert!(exact_solutions(&[1, 1, 1], 100).is_empty());
+    }
+
+    #[test]
+    fn exact_solutions_counts_reordered_and_regrouped_working_once() {
+        // act
+        let subtraction = exact_solutions(&[9, 7, 3], 5);
+        let division = exact_solutions(&[6, 3, 2], 4);
+
+        // assert
+        assert_eq!(subtraction, [String::from("3 + (9 - 7)")]);
+        assert_eq!(
+            division,
+            [
+                String::from("2 + (6 / 3)"),
+                String::from("2 * (6 / 3)"),
+                String::from("6 - 2")
+            ]
+        );
+    }
+
+    #[test]
+    fn reachable_values_includes_numbers_and_combinations() {
+        // arrange
+        let numbers = [2, 3, 7];
+
+        // act
+        let result = reachable_values(&numbers);
+
+        // assert
+        assert!(result.contains(&7));
+        assert!(result.contains(&42));
+        assert!(result.contains(&23));
+        assert!(!result.contains(&43));
+    }
+}
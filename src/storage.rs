@@ -0,0 +1,48 @@
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+const APP_DIRECTORY: &str = "countdown-numbers";
+
+/// Directory for saved game data, following the XDG base directory spec:
+/// `$XDG_DATA_HOME/countdown-numbers`, falling back to `~/.local/share/countdown-numbers`.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(value) if !value.is_empty() => PathBuf::from(value),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join(APP_DIRECTORY))
+}
+
+/// Path of `file_name` within the data directory
+pub fn data_file(file_name: &str) -> io::Result<PathBuf> {
+    data_dir()
+        .map(|value| value.join(file_name))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Unable to find a data directory, set `XDG_DATA_HOME` or `HOME`",
+            )
+        })
+}
+
+/// Lines of `path`, or none if the file does not exist yet
+pub fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    match File::open(path) {
+        Ok(file) => BufReader::new(file).lines().collect(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Appends `line` to `path`, creating the file and any parent directories first if needed.
+pub fn append_line(path: &Path, line: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
@@ -7,7 +7,7 @@ use rodio::{
 
 pub struct SoundEffects {
     pub start: Buffered<Decoder<File>>,
-    // pub end: Buffered<Decoder<File>>,
+    pub end: Buffered<Decoder<File>>,
     pub perfect: Buffered<Decoder<File>>,
     pub valid: Buffered<Decoder<File>>,
     pub firework: Buffered<Decoder<File>>,
@@ -29,7 +29,7 @@ impl Default for SoundEffects {
     fn default() -> Self {
         SoundEffects {
             start: buffer_sound_effect("./assets/start.mp3"),
-            //end: buffer_sound_effect("./assets/end.mp3"),
+            end: buffer_sound_effect("./assets/end.mp3"),
             perfect: buffer_sound_effect("./assets/perfect.mp3"),
             valid: buffer_sound_effect("./assets/valid.mp3"),
             firework: buffer_sound_effect("./assets/firework.mp3"),
@@ -0,0 +1,186 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+use crate::expression::{parse, tokenise, Token, TokenKind};
+
+/// How each token of a solution relates to the numbers picked for the round
+struct Analysis {
+    tokens: Vec<Token>,
+    /// For each token, `false` if it is a number which is not available to use
+    available: Vec<bool>,
+    /// Which of the selected numbers the solution uses
+    used: [bool; 6],
+}
+
+fn analyse(value_input: &str, selected_numbers: &[Option<u32>; 6]) -> Option<Analysis> {
+    let tokens = tokenise(value_input).ok()?;
+    let mut used = [false; 6];
+    let available = tokens
+        .iter()
+        .map(|token| {
+            let TokenKind::Number(value) = token.kind else {
+                return true;
+            };
+            let Some(index) = selected_numbers
+                .iter()
+                .zip(used)
+                .position(|(number, is_used)| *number == Some(value) && !is_used)
+            else {
+                return false;
+            };
+            used[index] = true;
+            true
+        })
+        .collect();
+
+    Some(Analysis {
+        tokens,
+        available,
+        used,
+    })
+}
+
+/// Style for each byte of `value_input`, from its tokens: numbers are green while available and
+/// red once unavailable or used too often, and operators are cyan.  Where the input cannot be
+/// parsed, such as an unmatched bracket or a misplaced operator, the part the parser stopped at
+/// is highlighted in red.
+pub fn input_styles(value_input: &str, selected_numbers: &[Option<u32>; 6]) -> Vec<Style> {
+    let mut styles = vec![Style::default(); value_input.len()];
+    if let Some(Analysis {
+        tokens, available, ..
+    }) = analyse(value_input, selected_numbers)
+    {
+        for (token, is_available) in tokens.iter().zip(available) {
+            let style = match token.kind {
+                TokenKind::Number(_) if is_available => Style::default().fg(Color::Green),
+                TokenKind::Number(_) => Style::default().fg(Color::Red),
+                TokenKind::Operator(_) => Style::default().fg(Color::Cyan),
+                TokenKind::OpenParenthesis | TokenKind::CloseParenthesis => Style::default(),
+            };
+            styles[token.span.clone()].fill(style);
+        }
+    }
+
+    if let Some(span) = parse(value_input).err().and_then(|error| error.span()) {
+        styles[span].fill(
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        );
+    }
+    styles
+}
+
+/// Which of `selected_numbers` the solution in `value_input` already uses
+pub fn used_numbers(value_input: &str, selected_numbers: &[Option<u32>; 6]) -> [bool; 6] {
+    analyse(value_input, selected_numbers).map_or([false; 6], |value| value.used)
+}
+
+/// Solution input as spans, grouping characters with the same style, and with the character
+/// under the cursor highlighted
+pub fn input_spans<'a>(value_input: &'a str, styles: &[Style], cursor: usize) -> Vec<Span<'a>> {
+    let cursor = cursor.min(value_input.len());
+    let cursor_modifier = Modifier::REVERSED | Modifier::SLOW_BLINK;
+    let mut spans: Vec<Span> = vec![Span::from("    ")];
+    let mut start = 0;
+    let mut current_style = None;
+
+    for (index, character) in value_input.char_indices() {
+        let mut style = styles.get(index).copied().unwrap_or_default();
+        if index == cursor {
+            style = style.add_modifier(cursor_modifier);
+        }
+        if current_style.is_some_and(|value| value != style) {
+            spans.push(Span::styled(
+                &value_input[start..index],
+                current_style.unwrap_or_default(),
+            ));
+            start = index;
+        }
+        current_style = Some(style);
+        if index + character.len_utf8() == value_input.len() {
+            spans.push(Span::styled(&value_input[start..], style));
+        }
+    }
+    if cursor == value_input.len() {
+        spans.push(Span::styled(
+            " ",
+            Style::default().add_modifier(cursor_modifier),
+        ));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_spans, input_styles, used_numbers};
+    use ratatui::style::{Color, Modifier, Style};
+
+    const SELECTED_NUMBERS: [Option<u32>; 6] =
+        [Some(25), Some(50), Some(3), Some(6), Some(7), Some(7)];
+
+    #[test]
+    fn input_styles_colours_tokens_by_role() {
+        // act
+        let styles = input_styles("(7 + 7 * 7) * 2)", &SELECTED_NUMBERS);
+
+        // assert
+        assert_eq!(styles[0], Style::default());
+        assert_eq!(styles[1].fg, Some(Color::Green));
+        assert_eq!(styles[3].fg, Some(Color::Cyan));
+        assert_eq!(styles[5].fg, Some(Color::Green));
+        assert_eq!(styles[9].fg, Some(Color::Red));
+        assert_eq!(styles[14].fg, Some(Color::Red));
+        assert_eq!(styles[15].bg, Some(Color::Red));
+    }
+
+    #[test]
+    fn input_styles_highlights_unclosed_parenthesis() {
+        // act
+        let styles = input_styles("((25 + 3)", &SELECTED_NUMBERS);
+
+        // assert
+        assert_eq!(styles[0].bg, Some(Color::Red));
+        assert_eq!(styles[1].bg, None);
+    }
+
+    #[test]
+    fn input_styles_highlights_misplaced_operator_and_unknown_character() {
+        // act
+        let misplaced_operator = input_styles("25 + * 3", &SELECTED_NUMBERS);
+        let unknown_character = input_styles("25 x 3", &SELECTED_NUMBERS);
+
+        // assert
+        assert_eq!(misplaced_operator[3].bg, None);
+        assert_eq!(misplaced_operator[5].bg, Some(Color::Red));
+        assert_eq!(unknown_character[3].bg, Some(Color::Red));
+    }
+
+    #[test]
+    fn used_numbers_marks_each_number_once() {
+        // act
+        let result = used_numbers("7 * (25 + 7) - 7", &SELECTED_NUMBERS);
+
+        // assert
+        assert_eq!(result, [true, false, false, false, true, true]);
+    }
+
+    #[test]
+    fn input_spans_groups_styled_characters_and_shows_cursor() {
+        // arrange
+        let value_input = "25 + 3";
+        let styles = input_styles(value_input, &SELECTED_NUMBERS);
+
+        // act
+        let spans = input_spans(value_input, &styles, 1);
+
+        // assert
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, ["    ", "2", "5", " ", "+", " ", "3"]);
+        assert!(spans[2].style.add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(spans[6].style.fg, Some(Color::Green));
+    }
+}
@@ -1,24 +1,45 @@
 pub mod audio;
+mod highlight;
 
 use std::f64::consts::PI;
 
-use crate::app::{App, CurrentScreen};
+use crate::{
+    app::{App, CurrentScreen, SolutionError},
+    calculator::{Calculator, InputMode},
+    conundrum::CONUNDRUM_POINTS,
+    episode::{self, EpisodeStage},
+    expression::Rules,
+    hint::HINT_COUNT,
+    letters::{self, LetterKind, WordError, LETTER_COUNT, MIN_CONSONANTS, MIN_VOWELS},
+    player::Player,
+    scoring::HINT_PENALTY,
+    solver::Solution,
+};
 
 use audio::SoundEffects;
 use rand::Rng;
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span, Text},
     widgets::{
         canvas::{Canvas, Circle},
-        Block, Borders, Padding, Paragraph, Widget, Wrap,
+        Block, Borders, Gauge, Padding, Paragraph, Widget, Wrap,
     },
     Frame,
 };
 use rodio::Sink;
 
+/// Earlier attempts listed below the solution input, newest last
+const MAX_CANDIDATES_SHOWN: usize = 4;
+
+/// Longest possible words listed on the letters round result screen
+const MAX_LONGEST_WORDS_SHOWN: usize = 5;
+
+const LETTERS_SCORING_RULES: &str =
+    "Only the longest valid word scores: a point per letter, or 18 for using all nine";
+
 const NUM_SPARK_COLOURS: usize = 11;
 const SPARK_COLOURS: [Color; NUM_SPARK_COLOURS] = [
     Color::Red,
@@ -92,7 +113,7 @@ impl Ui {
     }
 
     pub fn on_tick(&mut self, app: &mut App, sink: Option<&Sink>) {
-        if let Some(0) = app.check_solution() {
+        if is_any_solution_exact(app) {
             if let Some(value) = self.firework_tick_count {
                 if (value % 180) == 0 && value < 3600 {
                     match (value / 180) % 3 {
@@ -134,11 +155,23 @@ impl Ui {
                 let selected_numbers = create_selected_numbers_block(app);
                 frame.render_widget(selected_numbers, chunks[1]);
             }
+            CurrentScreen::PickingLetters | CurrentScreen::PlayingLetters => {
+                let selected_letters = create_selected_letters_block(app);
+                frame.render_widget(selected_letters, chunks[1]);
+            }
+            CurrentScreen::PlayingConundrum => {
+                let conundrum = create_conundrum_block(app);
+                frame.render_widget(conundrum, chunks[1]);
+            }
             CurrentScreen::Introduction => {
                 let objective = create_objective(app);
                 frame.render_widget(objective, chunks[1]);
             }
-            CurrentScreen::DisplayingResult => {}
+            CurrentScreen::DisplayingResult
+            | CurrentScreen::LettersResult
+            | CurrentScreen::ConundrumResult
+            | CurrentScreen::EpisodeSummary
+            | CurrentScreen::Statistics => {}
         }
 
         match app.current_screen {
@@ -158,23 +191,48 @@ impl Ui {
                 frame.render_widget(small_number_list, number_selection_chunks[1]);
             }
             CurrentScreen::Playing => {
-                let solution_attempt = create_solution_attempt_block(app);
-                frame.render_widget(solution_attempt, chunks[2]);
+                render_playing(frame, app, chunks[2], create_solution_attempt_block(app));
+            }
+            CurrentScreen::PickingLetters => {
+                let letter_selection = create_letter_selection(app);
+                frame.render_widget(letter_selection, chunks[2]);
+            }
+            CurrentScreen::PlayingLetters => {
+                render_playing(frame, app, chunks[2], create_word_attempt_block(app));
             }
-            CurrentScreen::DisplayingResult => {
+            CurrentScreen::PlayingConundrum => {
+                render_playing(frame, app, chunks[2], create_conundrum_attempt_block(app));
+            }
+            CurrentScreen::DisplayingResult
+            | CurrentScreen::LettersResult
+            | CurrentScreen::ConundrumResult => {
                 if self.firework_tick_count.is_none() {
                     self.firework_tick_count = Some(0);
                 }
-                let result_text = create_result_block_text(app);
+                let result_lines = match app.current_screen {
+                    CurrentScreen::LettersResult => create_letters_result_lines(app),
+                    CurrentScreen::ConundrumResult => create_conundrum_result_lines(app),
+                    _ => create_result_block_lines(app),
+                };
+                let result_text_height = u16::try_from(result_lines.len()).unwrap_or(u16::MAX);
+                let result_text = Paragraph::new(result_lines);
                 let result_chunks = Layout::default()
                     .direction(Direction::Vertical)
-                    .constraints([Constraint::Length(1), Constraint::Min(1)])
+                    .constraints([Constraint::Length(result_text_height), Constraint::Min(1)])
                     .split(chunks[2]);
                 let result_canvas = create_result_block_canvas(app, &self.sparks);
 
                 frame.render_widget(result_text, result_chunks[0]);
                 frame.render_widget(result_canvas, result_chunks[1]);
             }
+            CurrentScreen::Statistics => {
+                let statistics = create_statistics_block(app);
+                frame.render_widget(statistics, chunks[2]);
+            }
+            CurrentScreen::EpisodeSummary => {
+                let summary = create_episode_summary_block(app);
+                frame.render_widget(summary, chunks[2]);
+            }
         }
 
         let hint_footer = create_hint_footer(app);
@@ -191,11 +249,34 @@ impl Ui {
     }
 }
 
-fn create_title_block(app: &App) -> Paragraph {
-    let title_block = Block::default()
+/// Countdown above the block where the active player enters their answer
+fn render_playing(frame: &mut Frame, app: &App, area: Rect, attempt: Paragraph) {
+    let playing_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(area);
+    frame.render_widget(create_countdown_gauge(app), playing_chunks[0]);
+    frame.render_widget(attempt, playing_chunks[1]);
+}
+
+fn create_title_block(app: &App) -> Paragraph<'_> {
+    let mut title_block = Block::default()
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1))
         .style(Style::default());
+    if app.session_rounds > 0 {
+        let scores = match app.players.as_slice() {
+            [player] => format!("Score: {}", player.score),
+            players => players
+                .iter()
+                .map(|player| format!("{}: {}", player.name, player.score))
+                .collect::<Vec<String>>()
+                .join(" · "),
+        };
+        title_block = title_block.title(
+            Line::from(format!(" {scores} ({} rounds) ", app.session_rounds)).right_aligned(),
+        );
+    }
 
     let title_text = match app.current_screen {
         CurrentScreen::Introduction => "Numbers Game",
@@ -207,13 +288,62 @@ fn create_title_block(app: &App) -> Paragraph {
             }
         }
         CurrentScreen::Playing => "Solve the challenge",
-        CurrentScreen::DisplayingResult => "How did you do?",
+        CurrentScreen::DisplayingResult
+        | CurrentScreen::LettersResult
+        | CurrentScreen::ConundrumResult => "How did you do?",
+        CurrentScreen::Statistics => "Your statistics",
+        CurrentScreen::PickingLetters => {
+            if app.is_letter_selection_complete() {
+                "Hit (Enter) to start the letters round"
+            } else {
+                "Pick some letters"
+            }
+        }
+        CurrentScreen::PlayingLetters => "Find the longest word",
+        CurrentScreen::PlayingConundrum => "Solve the conundrum",
+        CurrentScreen::EpisodeSummary => "Final scores",
+    };
+    let title_text = match (&app.current_screen, app.daily) {
+        (CurrentScreen::Playing | CurrentScreen::DisplayingResult, Some(date)) => {
+            format!("{title_text} (daily puzzle for {date})")
+        }
+        (
+            CurrentScreen::Playing
+            | CurrentScreen::PlayingLetters
+            | CurrentScreen::PlayingConundrum,
+            None,
+        ) if is_hot_seat(app) => {
+            format!("{title_text} — {}’s turn", app.player().name)
+        }
+        _ => title_text.to_string(),
+    };
+    let title_text = match &app.episode {
+        Some(episode) => match episode.stage {
+            EpisodeStage::Round(index) => {
+                format!(
+                    "{title_text} (round {} of {})",
+                    index + 1,
+                    episode.rounds.len()
+                )
+            }
+            EpisodeStage::Finished => title_text,
+        },
+        None => title_text,
     };
 
     Paragraph::new(Text::styled(title_text, Style::default())).block(title_block)
 }
 
-fn create_selected_numbers_block(app: &App) -> Paragraph {
+/// `true` when several people share the terminal, so need telling whose turn it is
+fn is_hot_seat(app: &App) -> bool {
+    app.players
+        .iter()
+        .filter(|player| player.bot.is_none())
+        .count()
+        > 1
+}
+
+fn create_selected_numbers_block(app: &App) -> Paragraph<'_> {
     let mut selected_numbers_text = app.selected_numbers.into_iter().fold(
         vec![Span::styled("Numbers: ", Style::default())],
         |mut accum, val| {
@@ -224,13 +354,20 @@ fn create_selected_numbers_block(app: &App) -> Paragraph {
                 ));
             } else {
                 accum.push(Span::styled("_ ", Style::default().fg(Color::Green)));
-            };
+            }
             accum
         },
     );
 
     match app.current_screen {
-        CurrentScreen::Introduction => {}
+        CurrentScreen::Introduction
+        | CurrentScreen::Statistics
+        | CurrentScreen::PickingLetters
+        | CurrentScreen::PlayingLetters
+        | CurrentScreen::LettersResult
+        | CurrentScreen::PlayingConundrum
+        | CurrentScreen::ConundrumResult
+        | CurrentScreen::EpisodeSummary => {}
         CurrentScreen::PickingNumbers => {
             selected_numbers_text.push(Span::styled("    Target:", Style::default()));
             selected_numbers_text.push(Span::styled(" ???", Style::default().fg(Color::Green)));
@@ -242,13 +379,45 @@ fn create_selected_numbers_block(app: &App) -> Paragraph {
                 Style::default().fg(Color::Green),
             ));
         }
-    };
+    }
 
     Paragraph::new(Line::from(selected_numbers_text).centered())
         .block(Block::default().padding(Padding::top(1)))
 }
 
-fn create_objective(_app: &App) -> Paragraph {
+fn create_selected_letters_block(app: &App) -> Paragraph<'_> {
+    let letters_text = app.selected_letters.iter().fold(
+        vec![Span::styled("Letters: ", Style::default())],
+        |mut accum, letter| {
+            let text = letter.map_or_else(|| String::from("_ "), |value| format!("{value} "));
+            accum.push(Span::styled(text, Style::default().fg(Color::Green)));
+            accum
+        },
+    );
+
+    Paragraph::new(Line::from(letters_text).centered())
+        .block(Block::default().padding(Padding::top(1)))
+}
+
+fn create_conundrum_block(app: &App) -> Paragraph<'_> {
+    let anagram: Vec<String> = app
+        .conundrum
+        .iter()
+        .flat_map(|value| value.anagram.iter())
+        .map(char::to_string)
+        .collect();
+
+    Paragraph::new(
+        Line::from(vec![
+            Span::styled("Conundrum: ", Style::default()),
+            Span::styled(anagram.join(" "), Style::default().fg(Color::Green)),
+        ])
+        .centered(),
+    )
+    .block(Block::default().padding(Padding::top(1)))
+}
+
+fn create_objective(_app: &App) -> Paragraph<'_> {
     Paragraph::new(Span::styled(
         "Use your 6 (randomly picked) numbers with +, -, * and / operations to match the target number.",
         Style::default().fg(Color::Green),
@@ -257,8 +426,21 @@ fn create_objective(_app: &App) -> Paragraph {
     .block(Block::default().padding(Padding::horizontal(2)).padding(Padding::top(1)))
 }
 
-fn create_instructions(_app: &App) -> Paragraph {
-    Paragraph::new(vec![
+fn create_instructions(app: &App) -> Paragraph<'_> {
+    let solvable_targets_only = if app.settings.solvable_targets_only {
+        "on"
+    } else {
+        "off"
+    };
+    let time_limit = app.settings.time_limit.as_secs();
+    let rules = match (app.settings.rules, app.settings.effective_rules()) {
+        (Rules::Lenient, Rules::Strict) => {
+            "strict (lenient rules are off with step-by-step input, which only uses whole numbers)"
+        }
+        (_, Rules::Strict) => "strict (every step must give a positive whole number)",
+        (_, Rules::Lenient) => "lenient (only the final result must be a whole number)",
+    };
+    let mut lines = vec![
         Line::from("  — You pick 6 numbers, from 4 available large numbers and 20 small ones."),
         Line::from(
             "  — Combine your numbers with arithmetic operations to match the random target.",
@@ -268,11 +450,66 @@ fn create_instructions(_app: &App) -> Paragraph {
         Line::from(
             "  — If it’s not possible to reach the target exactly, get as close as you can.",
         ),
-    ])
-    .wrap(Wrap { trim: false })
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  (t) Only use targets that can be hit exactly: {solvable_targets_only}"),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!("  (l) Time limit: {time_limit}s"),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!("  (r) Rules: {rules}"),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!("  (p) Scoring: {}", app.settings.scoring),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!("  (i) Input: {}", app.settings.input_mode),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!("  (m) Players: {}", app.settings.player_mode),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            "  (w) Play a letters round: find the longest word in 9 letters",
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            "  (c) Play a conundrum: unscramble a nine-letter word",
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "  (e) Play a full game, (f) to change its format: {}",
+                episode::describe_rounds(&app.settings.episode_rounds)
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            "  (d) Play today’s daily puzzle",
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            "  (s) Statistics",
+            Style::default().fg(Color::Yellow),
+        )),
+    ];
+    if let Some(notice) = &app.notice {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {notice}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    Paragraph::new(lines).wrap(Wrap { trim: false })
 }
 
-fn create_large_number_selection(app: &App) -> Paragraph {
+fn create_large_number_selection(app: &App) -> Paragraph<'_> {
     let large_number_selection_text = app
         .available_large_numbers
         .into_iter()
@@ -292,7 +529,7 @@ fn create_large_number_selection(app: &App) -> Paragraph {
     .block(Block::default().padding(Padding::horizontal(1)))
 }
 
-fn create_small_number_line(numbers: &[Option<u32>]) -> Vec<Span> {
+fn create_small_number_line(numbers: &[Option<u32>]) -> Vec<Span<'_>> {
     numbers
         .iter()
         .copied()
@@ -306,7 +543,7 @@ fn create_small_number_line(numbers: &[Option<u32>]) -> Vec<Span> {
         .collect::<Vec<Span>>()
 }
 
-fn create_small_number_selection(app: &App) -> Paragraph {
+fn create_small_number_selection(app: &App) -> Paragraph<'_> {
     Paragraph::new(vec![
         Line::from("Small numbers ([):"),
         Line::from(create_small_number_line(&app.available_small_numbers[..7])).centered(),
@@ -319,34 +556,108 @@ fn create_small_number_selection(app: &App) -> Paragraph {
     .block(Block::default().padding(Padding::horizontal(1)))
 }
 
-fn create_hint_footer(app: &App) -> Paragraph {
+fn create_letter_selection(app: &App) -> Paragraph<'_> {
+    let pile_line = |label: &'static str, kind: LetterKind| {
+        let style = if letters::can_pick(kind, &app.selected_letters) {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        Line::from(Span::styled(
+            format!("{label}: {} left", app.letter_piles.remaining(kind)),
+            style,
+        ))
+    };
+
+    Paragraph::new(vec![
+        pile_line("Vowels (v)", LetterKind::Vowel),
+        pile_line("Consonants (c)", LetterKind::Consonant),
+        Line::from(""),
+        Line::from(format!(
+            "Pick {LETTER_COUNT} letters, with at least {MIN_VOWELS} vowels and \
+             {MIN_CONSONANTS} consonants."
+        )),
+        Line::from(LETTERS_SCORING_RULES),
+    ])
+    .wrap(Wrap { trim: false })
+    .block(Block::default().padding(Padding::horizontal(1)))
+}
+
+fn create_hint_footer(app: &App) -> Paragraph<'_> {
+    let seed_text = match &app.episode {
+        Some(episode) => match episode.next_round() {
+            Some(kind) => format!("Next up: {kind} round"),
+            None => String::from("Next up: the final scores"),
+        },
+        None => format!("Replay with --seed {}", app.seed),
+    };
+    let hint_key_text = if app.player().hints.len() < HINT_COUNT {
+        format!("(?) for a hint, costs {HINT_PENALTY} points")
+    } else {
+        String::from("Use ( + - / * ) to hit the target")
+    };
     let hint_text = match app.current_screen {
         CurrentScreen::Introduction => "Press (Enter) to skip",
         CurrentScreen::PickingNumbers => {
             if app.is_number_selection_complete() {
                 "Press (Enter) to start"
             } else {
-                "Pick 6 numbers [: small, ]: large"
+                "[: small, ]: large, 0-4: large count"
+            }
+        }
+        CurrentScreen::Playing => &hint_key_text,
+        CurrentScreen::DisplayingResult
+        | CurrentScreen::LettersResult
+        | CurrentScreen::ConundrumResult => &seed_text,
+        CurrentScreen::Statistics => "Rounds from every session are included",
+        CurrentScreen::PickingLetters => {
+            if app.is_letter_selection_complete() {
+                "Press (Enter) to start"
+            } else {
+                "v: vowel, c: consonant"
+            }
+        }
+        CurrentScreen::PlayingLetters => "Type your word, (Backspace) to delete",
+        CurrentScreen::EpisodeSummary => "Thanks for playing",
+        CurrentScreen::PlayingConundrum => {
+            if app.player().buzz_time.is_some() {
+                "Type your answer, (Backspace) to delete"
+            } else {
+                "(Space) to buzz in, (Enter) to pass"
             }
         }
-        CurrentScreen::Playing => "Use ( + - / * ) to hit the target",
-        CurrentScreen::DisplayingResult => "",
     };
 
-    Paragraph::new(Line::from(hint_text)).block(
+    Paragraph::new(Line::from(hint_text.to_string())).block(
         Block::default()
             .borders(Borders::ALL)
             .padding(Padding::horizontal(1)),
     )
 }
 
-fn create_key_notes_footer(app: &App) -> Paragraph {
+fn create_key_notes_footer(app: &App) -> Paragraph<'_> {
     let hint_text = match app.current_screen {
-        CurrentScreen::Introduction | CurrentScreen::PickingNumbers => {
-            "(q) to quit, (Enter) to start"
-        }
+        CurrentScreen::Introduction
+        | CurrentScreen::PickingNumbers
+        | CurrentScreen::PickingLetters => "(q) to quit, (Enter) to start",
         CurrentScreen::Playing => "(q) to quit, (Enter) to submit",
-        CurrentScreen::DisplayingResult => "(q) to quit, (Enter) to play again",
+        CurrentScreen::PlayingLetters | CurrentScreen::PlayingConundrum => {
+            "(Esc) to quit, (Enter) to submit"
+        }
+        CurrentScreen::DisplayingResult
+        | CurrentScreen::LettersResult
+        | CurrentScreen::ConundrumResult
+            if app.episode.is_some() =>
+        {
+            "(q) to quit, (Enter) to carry on"
+        }
+        CurrentScreen::DisplayingResult => "(q) to quit, (Enter) to play again, (s) stats",
+        CurrentScreen::LettersResult | CurrentScreen::ConundrumResult => {
+            "(q) to quit, (Enter) to play again"
+        }
+        CurrentScreen::Statistics | CurrentScreen::EpisodeSummary => {
+            "(q) to quit, (Enter) to go back"
+        }
     };
     let current_keys_hint = Span::styled(hint_text, Style::default().fg(Color::Yellow));
 
@@ -357,41 +668,635 @@ fn create_key_notes_footer(app: &App) -> Paragraph {
     )
 }
 
-fn create_solution_attempt_block(app: &App) -> Paragraph {
-    let hint = Line::from(Span::styled(
-        "Enter your solution here (using 0-9, +, -, *, / and ()):",
-        Style::default(),
+fn create_countdown_gauge(app: &App) -> Gauge<'_> {
+    let time_limit = app.settings.time_limit.as_secs_f64();
+    let time_remaining = app.time_remaining.as_secs_f64();
+    let ratio = if time_limit > 0.0 {
+        (time_remaining / time_limit).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let colour = if app.time_remaining.as_secs() < 10 {
+        Color::Red
+    } else {
+        Color::Green
+    };
+
+    Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1))
+                .title(" Time "),
+        )
+        .gauge_style(Style::default().fg(colour))
+        .ratio(ratio)
+        .label(format!("{}s", app.time_remaining.as_secs_f64().ceil()))
+}
+
+fn create_solution_attempt_block(app: &App) -> Paragraph<'_> {
+    let player = app.player();
+    let (hint_text, input_lines) = match app.settings.input_mode {
+        InputMode::Expression => (
+            "Enter your solution here (using 0-9, +, -, *, / and ()):",
+            create_expression_input_lines(app),
+        ),
+        InputMode::Calculator => (
+            "Pick a number (1-6), an operator, then another number. (Backspace) undoes:",
+            create_calculator_input_lines(app),
+        ),
+    };
+    let hint = if is_hot_seat(app) {
+        Line::from(format!("{}, {}", player.name, hint_text.to_lowercase()))
+    } else {
+        Line::from(hint_text)
+    };
+
+    let mut lines = vec![hint, Line::from("")];
+    lines.extend(input_lines);
+    if let Some(best) = &player.best_attempt {
+        let distance_text = match best.distance {
+            0 => String::from("hits the target"),
+            value => format!("{value} away"),
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    Best so far: {} ({distance_text}), kept if you submit something worse",
+                best.expression
+            ),
+            Style::default().fg(Color::Green),
+        )));
+    }
+    lines.extend(player.hints.iter().map(|value| {
+        Line::from(Span::styled(
+            format!("    💡 {value}"),
+            Style::default().fg(Color::Yellow),
+        ))
+    }));
+    Paragraph::new(lines)
+}
+
+fn create_expression_input_lines(app: &App) -> Vec<Line<'_>> {
+    let player = app.player();
+    let styles = highlight::input_styles(&player.value_input, &app.selected_numbers);
+    let mut input_feedback = highlight::input_spans(&player.value_input, &styles, player.cursor);
+    input_feedback.push(Span::styled(
+        &player.feedback,
+        Style::default().fg(Color::Green),
     ));
-    let input_text = if app.value_input.is_empty() {
-        Span::styled("    _", Style::default().add_modifier(Modifier::SLOW_BLINK))
+    let input_feedback = Line::from(input_feedback);
+    let error_message = match app.check_solution() {
+        Ok(_) | Err(SolutionError::Empty) => Line::from(""),
+        Err(error) => Line::from(Span::styled(
+            format!("    {error}"),
+            Style::default().fg(Color::Red),
+        )),
+    };
+    let mut lines = vec![
+        create_numbers_remaining_line(app),
+        input_feedback,
+        error_message,
+    ];
+
+    let history = &player.input_history;
+    if !history.candidates.is_empty() {
+        lines.push(Line::from(
+            "    Earlier attempts (↑/↓ to recall, Ctrl-Z/Ctrl-Y to undo/redo):",
+        ));
+    }
+    let first_shown = history
+        .candidates
+        .len()
+        .saturating_sub(MAX_CANDIDATES_SHOWN);
+    for (index, candidate) in history.candidates.iter().enumerate().skip(first_shown) {
+        let distance_text = match candidate.distance {
+            0 => String::from("hits the target"),
+            value => format!("{value} away"),
+        };
+        let style = if history.position == Some(index) {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("      {}", candidate.expression), style),
+            Span::styled(
+                format!("  ({distance_text})"),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// Strip of the selected numbers, with any the solution already uses crossed out
+fn create_numbers_remaining_line(app: &App) -> Line<'_> {
+    let used = highlight::used_numbers(&app.player().value_input, &app.selected_numbers);
+    let mut spans = vec![Span::from("    Numbers left: ")];
+    for (number, is_used) in app.selected_numbers.iter().zip(used) {
+        let Some(number) = number else {
+            continue;
+        };
+        let style = if is_used {
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        spans.push(Span::styled(number.to_string(), style));
+        spans.push(Span::from(" "));
+    }
+    Line::from(spans)
+}
+
+fn create_calculator_input_lines(app: &App) -> Vec<Line<'_>> {
+    let player = app.player();
+    let new_calculator;
+    let calculator = if let Some(value) = &player.calculator {
+        value
     } else {
-        Span::from(format!("    {}", &app.value_input))
+        let numbers: Vec<u32> = app.selected_numbers.iter().flatten().copied().collect();
+        new_calculator = Calculator::new(&numbers);
+        &new_calculator
+    };
+
+    let mut tiles = vec![Span::from("    ")];
+    for (index, tile) in calculator.tiles.iter().enumerate() {
+        let style = if calculator.left == Some(index) {
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        tiles.push(Span::from(format!("({}) ", index + 1)));
+        tiles.push(Span::styled(tile.value.to_string(), style));
+        tiles.push(Span::from("  "));
+    }
+    if let (Some(left), Some(operator)) = (calculator.left, calculator.operator) {
+        tiles.push(Span::from(format!(
+            "   {} {operator} _",
+            calculator.tiles[left].value
+        )));
+    }
+
+    let mut lines = vec![Line::from(tiles)];
+    lines.extend(
+        calculator
+            .steps
+            .iter()
+            .map(|step| Line::from(format!("      {step}"))),
+    );
+    let answer = match player.value_input.as_str() {
+        "" => String::from("    Answer: —"),
+        value => format!("    Answer: {value}"),
+    };
+    lines.push(Line::from(vec![
+        Span::from(answer),
+        Span::styled(&player.feedback, Style::default().fg(Color::Green)),
+    ]));
+    if let Some(error) = &calculator.error {
+        lines.push(Line::from(Span::styled(
+            format!("    {error}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines
+}
+
+fn create_word_attempt_block(app: &App) -> Paragraph<'_> {
+    let player = app.player();
+    let hint_text = "Type the longest word you can make from the letters:";
+    let hint = if is_hot_seat(app) {
+        Line::from(format!("{}, {}", player.name, hint_text.to_lowercase()))
+    } else {
+        Line::from(hint_text)
+    };
+    let feedback = match app.check_word(&player.value_input) {
+        Ok(length) => Line::from(Span::styled(
+            format!("    ✅ {length} letters"),
+            Style::default().fg(Color::Green),
+        )),
+        Err(WordError::Empty) => Line::from(""),
+        Err(error) => Line::from(Span::styled(
+            format!("    {error}"),
+            Style::default().fg(Color::Red),
+        )),
     };
 
-    let input_feedback = Line::from(vec![
-        input_text,
-        Span::styled(&app.feedback, Style::default().fg(Color::Green)),
+    Paragraph::new(vec![
+        hint,
+        Line::from(""),
+        Line::from(highlight::input_spans(
+            &player.value_input,
+            &[],
+            player.cursor,
+        )),
+        feedback,
+    ])
+}
+
+fn create_conundrum_attempt_block(app: &App) -> Paragraph<'_> {
+    let player = app.player();
+    let Some(buzz_time) = player.buzz_time else {
+        let hint_text = "Press (Space) to buzz in as soon as you see the word";
+        let hint = if is_hot_seat(app) {
+            format!("{}, {}", player.name, hint_text.to_lowercase())
+        } else {
+            String::from(hint_text)
+        };
+        return Paragraph::new(Line::from(hint));
+    };
+
+    Paragraph::new(vec![
+        Line::from(format!(
+            "Buzzed in after {:.1}s, the clock is stopped. Type the word within {}s:",
+            buzz_time.as_secs_f64(),
+            app.answer_time_remaining.as_secs_f64().ceil()
+        )),
+        Line::from(""),
+        Line::from(highlight::input_spans(
+            &player.value_input,
+            &[],
+            player.cursor,
+        )),
+    ])
+}
+
+fn create_points_text(player: &Player) -> String {
+    let points_text = match player.round_points {
+        Some(1) => String::from("  +1 point"),
+        Some(value) => format!("  +{value} points"),
+        None => String::new(),
+    };
+    match player.hints.len() {
+        0 => points_text,
+        1 => format!("{points_text} (1 hint used)"),
+        count => format!("{points_text} ({count} hints used)"),
+    }
+}
+
+fn create_round_winner_text(app: &App) -> String {
+    let winners: Vec<&str> = app
+        .players
+        .iter()
+        .filter(|player| player.round_points.is_some_and(|value| value > 0))
+        .map(|player| player.name.as_str())
+        .collect();
+    match winners.as_slice() {
+        [] => String::from("Nobody scored this round 🎲"),
+        [winner] => format!("{winner} wins the round 🏆"),
+        _ => String::from("It’s a tie, points all round 🤝"),
+    }
+}
+
+fn create_result_block_lines(app: &App) -> Vec<Line<'_>> {
+    let (best_text, exact_note) = match &app.solution {
+        Some(Solution {
+            value,
+            distance,
+            expression,
+            ..
+        }) => (
+            format!("Best possible: {expression} = {value}"),
+            if *distance == 0 {
+                "An exact solution was possible."
+            } else {
+                "No exact solution was possible this time."
+            },
+        ),
+        None => (String::new(), ""),
+    };
+
+    let mut lines = if let [player] = app.players.as_slice() {
+        let solution_text = match app.check_input(&player.value_input) {
+            Ok(value) => match value {
+                0 => String::from("You nailed it 🔨. You hit the target!"),
+                // same bands as the points in `Scoring::Countdown`
+                1..=5 => format!("Great result 🥈 just {value} from the target!"),
+                6..=10 => format!("Nice result 🥉 {value} from the target!"),
+                _ => format!("You got within {value} of the target 🏹"),
+            },
+            Err(_) => String::from("Unlucky! You can always try again 🎲"),
+        };
+        let answer_text = match player.value_input.trim() {
+            "" => String::from("Your answer: —"),
+            value => format!("Your answer: {value}"),
+        };
+        let mut lines = vec![
+            Line::from(format!("{solution_text}{}", create_points_text(player))).centered(),
+            Line::from(vec![
+                Span::styled(answer_text, Style::default()),
+                Span::styled("    ", Style::default()),
+                Span::styled(best_text, Style::default().fg(Color::Green)),
+            ])
+            .centered(),
+        ];
+        lines.extend(create_final_input_line(player));
+        lines
+    } else {
+        // every player's answer is revealed together, once all have submitted
+        let mut lines = vec![Line::from(create_round_winner_text(app)).centered()];
+        lines.extend(app.players.iter().flat_map(|player| {
+            let distance_text = match app.check_input(&player.value_input) {
+                Ok(0) => String::from("hit the target"),
+                Ok(value) => format!("{value} away"),
+                Err(_) => String::from("no valid answer"),
+            };
+            let answer_text = match player.value_input.trim() {
+                "" => "—",
+                value => value,
+            };
+            std::iter::once(
+                Line::from(format!(
+                    "{}: {answer_text} ({distance_text}){}",
+                    player.name,
+                    create_points_text(player)
+                ))
+                .centered(),
+            )
+            .chain(create_final_input_line(player))
+        }));
+        lines.push(
+            Line::from(Span::styled(best_text, Style::default().fg(Color::Green))).centered(),
+        );
+        lines
+    };
+
+    lines.extend([
+        Line::from(exact_note).centered(),
+        Line::from(Span::styled(
+            app.puzzle_code()
+                .map(|value| format!("Share this puzzle with code {value}"))
+                .unwrap_or_default(),
+            Style::default().fg(Color::Yellow),
+        ))
+        .centered(),
+        Line::from(Span::styled(
+            app.notice.clone().unwrap_or_default(),
+            Style::default().fg(Color::Red),
+        ))
+        .centered(),
     ]);
-    Paragraph::new(vec![hint, Line::from(""), input_feedback])
+    lines
 }
 
-fn create_result_block_text(app: &App) -> Paragraph {
-    let solution_text = match app.check_solution() {
-        Some(value) => match value {
-            0 => String::from("You nailed it 🔨. You hit the target!"),
-            1..=5 => format!("Awesome result 🏅 only {value} from the target!"),
-            6 => format!("Great result 🥈 just {value} from the target!"),
-            7..=10 => format!("Nice result 🥉 {value} from the target!"),
-            _ => format!("You got within {value} of the target 🏹"),
-        },
-        None => String::from("Unlucky! You can always try again 🎲"),
+fn create_letters_result_lines(app: &App) -> Vec<Line<'_>> {
+    let longest_possible = app.longest_words.first().map_or(0, |word| word.len());
+    let longest_text = if app.longest_words.is_empty() {
+        String::from("No word in the list can be made from these letters")
+    } else {
+        let words: Vec<String> = app
+            .longest_words
+            .iter()
+            .take(MAX_LONGEST_WORDS_SHOWN)
+            .map(|word| word.to_ascii_uppercase())
+            .collect();
+        format!(
+            "Longest in the word list ({longest_possible} letters): {}",
+            words.join(", ")
+        )
     };
-    Paragraph::new(Line::from(solution_text).centered())
+    let word_text = |player: &Player| match app.check_word(&player.value_input) {
+        Ok(length) => format!("{} ({length} letters)", player.value_input.trim()),
+        Err(WordError::Empty) => String::from("—"),
+        Err(error) => format!("{} ({error})", player.value_input.trim()),
+    };
+
+    let mut lines = if let [player] = app.players.as_slice() {
+        let result_text = match app.check_word(&player.value_input) {
+            Ok(length) if length == longest_possible => {
+                String::from("You found the longest possible word 🔨")
+            }
+            Ok(length) => format!("Nice word 🏅 {length} letters long"),
+            Err(_) => String::from("Unlucky! You can always try again 🎲"),
+        };
+        vec![
+            Line::from(format!("{result_text}{}", create_points_text(player))).centered(),
+            Line::from(format!("Your word: {}", word_text(player))).centered(),
+        ]
+    } else {
+        let mut lines = vec![Line::from(create_round_winner_text(app)).centered()];
+        lines.extend(app.players.iter().map(|player| {
+            Line::from(format!(
+                "{}: {}{}",
+                player.name,
+                word_text(player),
+                create_points_text(player)
+            ))
+            .centered()
+        }));
+        lines
+    };
+
+    lines.extend([
+        Line::from(Span::styled(
+            longest_text,
+            Style::default().fg(Color::Green),
+        ))
+        .centered(),
+        Line::from(LETTERS_SCORING_RULES).centered(),
+        Line::from(Span::styled(
+            app.notice.clone().unwrap_or_default(),
+            Style::default().fg(Color::Red),
+        ))
+        .centered(),
+    ]);
+    lines
+}
+
+fn create_conundrum_result_lines(app: &App) -> Vec<Line<'_>> {
+    let word = app
+        .conundrum
+        .as_ref()
+        .map(|value| value.word.to_ascii_uppercase())
+        .unwrap_or_default();
+    let answer_text = |player: &Player| {
+        let answer = match player.value_input.trim() {
+            "" => "—",
+            value => value,
+        };
+        match (player.buzz_time, app.check_conundrum(&player.value_input)) {
+            (Some(buzz_time), true) => {
+                format!("{answer} (correct, in {:.1}s)", buzz_time.as_secs_f64())
+            }
+            (Some(_), false) => format!("{answer} (wrong)"),
+            (None, _) => String::from("did not buzz in"),
+        }
+    };
+
+    let mut lines = if let [player] = app.players.as_slice() {
+        let result_text = if player.round_points.is_some_and(|value| value > 0) {
+            "You solved the conundrum 🔨"
+        } else {
+            "Unlucky! You can always try again 🎲"
+        };
+        vec![
+            Line::from(format!("{result_text}{}", create_points_text(player))).centered(),
+            Line::from(format!("Your answer: {}", answer_text(player))).centered(),
+        ]
+    } else {
+        let mut lines = vec![Line::from(create_round_winner_text(app)).centered()];
+        lines.extend(app.players.iter().map(|player| {
+            Line::from(format!(
+                "{}: {}{}",
+                player.name,
+                answer_text(player),
+                create_points_text(player)
+            ))
+            .centered()
+        }));
+        lines
+    };
+
+    lines.extend([
+        Line::from(Span::styled(
+            format!("The word was {word}"),
+            Style::default().fg(Color::Green),
+        ))
+        .centered(),
+        Line::from(format!(
+            "Whoever buzzes in first with the right word scores {CONUNDRUM_POINTS} points"
+        ))
+        .centered(),
+        Line::from(Span::styled(
+            app.notice.clone().unwrap_or_default(),
+            Style::default().fg(Color::Red),
+        ))
+        .centered(),
+    ]);
+    lines
+}
+
+/// Notes when a player's best attempt was scored in place of what they finally submitted
+fn create_final_input_line(player: &Player) -> Option<Line<'_>> {
+    let final_input = match player.final_input.as_deref()?.trim() {
+        "" => "—",
+        value => value,
+    };
+    Some(
+        Line::from(Span::styled(
+            format!("Your best attempt was kept, rather than your final input: {final_input}"),
+            Style::default().fg(Color::Yellow),
+        ))
+        .centered(),
+    )
+}
+
+fn create_statistics_block(app: &App) -> Paragraph<'_> {
+    let Some(statistics) = &app.statistics else {
+        return Paragraph::new("");
+    };
+    if statistics.rounds_played == 0 {
+        return Paragraph::new(Line::from("  No rounds played yet."));
+    }
+
+    let exact_hit_rate = statistics
+        .exact_hit_rate()
+        .map(|value| format!("{value:.0}%"))
+        .unwrap_or_default();
+    let average_distance = statistics
+        .average_distance
+        .map_or_else(|| String::from("—"), |value| format!("{value:.1}"));
+    let line = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {label}: "), Style::default()),
+            Span::styled(value, Style::default().fg(Color::Green)),
+        ])
+    };
+
+    Paragraph::new(vec![
+        line("Rounds played", statistics.rounds_played.to_string()),
+        line("Exact hits", exact_hit_rate),
+        line("Average distance from target", average_distance),
+        line(
+            "Current streak of exact hits",
+            statistics.current_streak.to_string(),
+        ),
+    ])
+    .block(Block::default().padding(Padding::top(1)))
+}
+
+fn create_episode_summary_block(app: &App) -> Paragraph<'_> {
+    let Some(episode) = &app.episode else {
+        return Paragraph::new("");
+    };
+    let points_text = |points: &[u32]| match (app.players.as_slice(), points) {
+        ([_], [value]) => format!("{value} points"),
+        (players, points) => players
+            .iter()
+            .zip(points)
+            .map(|(player, value)| format!("{}: {value}", player.name))
+            .collect::<Vec<String>>()
+            .join(" · "),
+    };
+
+    let mut lines: Vec<Line> = episode
+        .scoreboard
+        .iter()
+        .enumerate()
+        .map(|(index, round)| {
+            Line::from(vec![
+                Span::from(format!("  Round {}, {}: ", index + 1, round.kind)),
+                Span::styled(
+                    points_text(&round.points),
+                    Style::default().fg(Color::Green),
+                ),
+            ])
+        })
+        .collect();
+    let totals: Vec<u32> = app.players.iter().map(|player| player.score).collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::from("  Total: "),
+        Span::styled(points_text(&totals), Style::default().fg(Color::Green)),
+    ]));
+
+    if app.players.len() > 1 {
+        let best = totals.iter().max().copied().unwrap_or_default();
+        let winners: Vec<&str> = app
+            .players
+            .iter()
+            .filter(|player| player.score == best)
+            .map(|player| player.name.as_str())
+            .collect();
+        let winner_text = match winners.as_slice() {
+            [winner] => format!("  {winner} won the game 🏆"),
+            _ => String::from("  It’s a draw 🤝"),
+        };
+        lines.push(Line::from(winner_text));
+    }
+
+    Paragraph::new(lines).block(Block::default().padding(Padding::top(1)))
+}
+
+/// `true` if any player hit the target exactly, found the longest possible word or solved the
+/// conundrum, which is worth celebrating
+fn is_any_solution_exact(app: &App) -> bool {
+    if matches!(app.current_screen, CurrentScreen::ConundrumResult) {
+        return app
+            .players
+            .iter()
+            .any(|player| player.buzz_time.is_some() && app.check_conundrum(&player.value_input));
+    }
+    if matches!(app.current_screen, CurrentScreen::LettersResult) {
+        let Some(longest_possible) = app.longest_words.first().map(|word| word.len()) else {
+            return false;
+        };
+        return app
+            .players
+            .iter()
+            .any(|player| app.check_word(&player.value_input) == Ok(longest_possible));
+    }
+    app.players
+        .iter()
+        .any(|player| app.check_input(&player.value_input) == Ok(0))
 }
 
 fn create_result_block_canvas<'a>(app: &'a App, sparks: &'a [Spark]) -> impl Widget + 'a {
-    match app.check_solution() {
-        Some(0) => Canvas::default()
+    if is_any_solution_exact(app) {
+        Canvas::default()
             .block(Block::default())
             .marker(symbols::Marker::Dot)
             .paint(move |ctx| {
@@ -411,8 +1316,9 @@ fn create_result_block_canvas<'a>(app: &'a App, sparks: &'a [Spark]) -> impl Wid
                 }
             })
             .x_bounds([-100.0, 100.0])
-            .y_bounds([-50.0, 50.0]),
-        None | Some(_) => Canvas::default(),
+            .y_bounds([-50.0, 50.0])
+    } else {
+        Canvas::default()
     }
 }
 
@@ -506,6 +1412,29 @@ mod tests {
         assert_eq!(buf, expected);
     }
 
+    #[test]
+    fn create_title_displays_session_score_once_a_round_is_scored() {
+        // arrange
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::DisplayingResult;
+        app.players[0].score = 17;
+        app.session_rounds = 2;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 3));
+
+        let title_block = create_title_block(&app);
+
+        // act
+        title_block.render(buf.area, &mut buf);
+
+        // assert
+        let expected = Buffer::with_lines(vec![
+            "┌──────────────────────────────────────────────────────── Score: 17 (2 rounds) ┐",
+            "│ How did you do?                                                              │",
+            "└──────────────────────────────────────────────────────────────────────────────┘",
+        ]);
+        assert_eq!(buf, expected);
+    }
+
     #[test]
     fn create_title_displays_as_expected_in_result_view() {
         // arrange